@@ -1,19 +1,362 @@
 //! Benchmarking setup for pallet-template
 
 use super::*;
+use crate::game_logic::{
+	GameId, GameMetadata, GameMode, GameMovement, GameState, GameStatus, MaxMetadataLen,
+	SecretGameMovement, NATIVE_ASSET,
+};
 
 #[allow(unused)]
 use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	dispatch::DispatchResult,
+	sp_runtime::traits::{Bounded, One},
+	sp_std::vec,
+	traits::{Currency, Hooks},
+	weights::Weight,
+	BoundedVec,
+};
 use frame_system::RawOrigin;
 
+const SEED: u32 = 0;
+
+fn bet<T: Config>() -> BalanceOf<T> {
+	T::MinBet::get().max(100u32.into())
+}
+
+fn funded<T: Config>(who: T::AccountId) -> T::AccountId {
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
+
+/// Has `who` join `game_id` committing to `movement` with the smallest accepted secret.
+fn join<T: Config>(who: &T::AccountId, game_id: GameId, movement: GameMovement) -> DispatchResult {
+	let secret_movement = SecretGameMovement::new(&movement, T::MinSecret::get(), game_id, who);
+	Template::<T>::play_game(
+		RawOrigin::Signed(who.clone()).into(),
+		game_id,
+		secret_movement,
+		bet::<T>(),
+		NATIVE_ASSET,
+		None,
+	)
+}
+
+/// Moves past `MinRevealDelay` so that the movements committed so far can be revealed.
+fn wait_reveal_delay<T: Config>() {
+	let now = frame_system::Pallet::<T>::block_number();
+	frame_system::Pallet::<T>::set_block_number(now + T::MinRevealDelay::get());
+}
+
+fn reveal<T: Config>(
+	who: &T::AccountId,
+	game_id: GameId,
+	movement: GameMovement,
+) -> DispatchResult {
+	wait_reveal_delay::<T>();
+	Template::<T>::reveal_move(
+		RawOrigin::Signed(who.clone()).into(),
+		game_id,
+		movement,
+		T::MinSecret::get(),
+		true,
+	)
+}
+
+/// Has `who` reveal their movement in `game_id` without settling the round.
+fn reveal_without_settling<T: Config>(
+	who: &T::AccountId,
+	game_id: GameId,
+	movement: GameMovement,
+) -> DispatchResult {
+	wait_reveal_delay::<T>();
+	Template::<T>::reveal_move(
+		RawOrigin::Signed(who.clone()).into(),
+		game_id,
+		movement,
+		T::MinSecret::get(),
+		false,
+	)
+}
+
+/// Creates game 0 played over `rounds` rounds and has a first player join it with Rock.
+fn game_with_opponent<T: Config>(rounds: u8) -> Result<T::AccountId, &'static str> {
+	let opponent = funded::<T>(account("opponent", 0, SEED));
+	Template::<T>::do_create_game(&opponent, GameMode::Classic, rounds, false, None, None, None)?;
+	join::<T>(&opponent, 0, GameMovement::Rock)?;
+	Ok(opponent)
+}
+
+fn is_settled<T: Config>(game_id: GameId) -> bool {
+	Games::<T>::get(game_id).map_or(false, |game_state| game_state.status == GameStatus::Settled)
+}
+
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
+	create_game {
+		let caller = funded::<T>(whitelisted_caller());
+		let metadata: GameMetadata = vec![0; MaxMetadataLen::get() as usize].try_into().unwrap();
+	}: _(RawOrigin::Signed(caller), GameMode::Classic, 1, false, None, None, Some(metadata))
+	verify {
+		assert!(Games::<T>::contains_key(0));
+	}
+
+	create_games_batch {
+		let n in 1 .. T::MaxBatch::get();
+		let caller = funded::<T>(whitelisted_caller());
+	}: _(RawOrigin::Signed(caller), GameMode::Classic, 1, n)
+	verify {
+		assert_eq!(NextGameId::<T>::get(), GameId::from(n));
+	}
+
+	play_game {
+		game_with_opponent::<T>(1)?;
+		let caller = funded::<T>(whitelisted_caller());
+		let secret_movement =
+			SecretGameMovement::new(&GameMovement::Paper, T::MinSecret::get(), 0, &caller);
+	}: _(RawOrigin::Signed(caller), 0, secret_movement, bet::<T>(), NATIVE_ASSET, None)
+	verify {
+		let status = Games::<T>::get(0).map(|game_state| game_state.status);
+		assert_eq!(status, Some(GameStatus::AwaitingReveal));
+	}
+
+	commit_move {
+		let opponent = game_with_opponent::<T>(3)?;
+		let caller = funded::<T>(whitelisted_caller());
+		join::<T>(&caller, 0, GameMovement::Rock)?;
+		// A drawn first round leaves the game waiting for new movements.
+		reveal::<T>(&opponent, 0, GameMovement::Rock)?;
+		reveal::<T>(&caller, 0, GameMovement::Rock)?;
+		Template::<T>::commit_move(
+			RawOrigin::Signed(opponent.clone()).into(),
+			0,
+			SecretGameMovement::new(&GameMovement::Rock, T::MinSecret::get(), 0, &opponent),
+		)?;
+		let secret_movement =
+			SecretGameMovement::new(&GameMovement::Paper, T::MinSecret::get(), 0, &caller);
+	}: _(RawOrigin::Signed(caller), 0, secret_movement)
+	verify {
+		let status = Games::<T>::get(0).map(|game_state| game_state.status);
+		assert_eq!(status, Some(GameStatus::AwaitingReveal));
+	}
+
+	recommit {
+		let caller = funded::<T>(whitelisted_caller());
+		Template::<T>::do_create_game(&caller, GameMode::Classic, 1, false, None, None, None)?;
+		join::<T>(&caller, 0, GameMovement::Rock)?;
+		let secret_movement =
+			SecretGameMovement::new(&GameMovement::Paper, T::MinSecret::get(), 0, &caller);
+	}: _(RawOrigin::Signed(caller.clone()), 0, secret_movement.clone())
+	verify {
+		let committed = Games::<T>::get(0)
+			.and_then(|game_state| game_state.player1)
+			.and_then(|player_movement| player_movement.secret_movement);
+		assert_eq!(committed, Some(secret_movement));
+	}
+
+	cancel_game {
+		let caller = funded::<T>(whitelisted_caller());
+		Template::<T>::do_create_game(&caller, GameMode::Classic, 1, false, None, None, None)?;
+		join::<T>(&caller, 0, GameMovement::Rock)?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(!Games::<T>::contains_key(0));
+	}
+
+	reveal_winner {
+		game_with_opponent::<T>(1)?;
+		let caller = funded::<T>(whitelisted_caller());
+		join::<T>(&caller, 0, GameMovement::Paper)?;
+		wait_reveal_delay::<T>();
+		let secret = T::MinSecret::get();
+	}: _(RawOrigin::Signed(caller), 0, GameMovement::Paper, secret, GameMovement::Rock, secret)
+	verify {
+		assert!(is_settled::<T>(0));
+	}
+
+	reveal_move {
+		let opponent = game_with_opponent::<T>(1)?;
+		let caller = funded::<T>(whitelisted_caller());
+		join::<T>(&caller, 0, GameMovement::Paper)?;
+		reveal::<T>(&opponent, 0, GameMovement::Rock)?;
+	}: _(RawOrigin::Signed(caller), 0, GameMovement::Paper, T::MinSecret::get(), true)
+	verify {
+		assert!(is_settled::<T>(0));
+	}
+
+	settle_game {
+		let opponent = game_with_opponent::<T>(1)?;
+		let player = funded::<T>(account("player", 0, SEED));
+		join::<T>(&player, 0, GameMovement::Paper)?;
+		reveal::<T>(&opponent, 0, GameMovement::Rock)?;
+		reveal_without_settling::<T>(&player, 0, GameMovement::Paper)?;
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(is_settled::<T>(0));
+	}
+
+	settle_games_batch {
+		let n in 1 .. T::MaxBatch::get();
+		for game_id in 0..GameId::from(n) {
+			let opponent = funded::<T>(account("opponent", game_id as u32, SEED));
+			let player = funded::<T>(account("player", game_id as u32, SEED));
+			Template::<T>::do_create_game(&opponent, GameMode::Classic, 1, false, None, None, None)?;
+			join::<T>(&opponent, game_id, GameMovement::Rock)?;
+			join::<T>(&player, game_id, GameMovement::Paper)?;
+			reveal::<T>(&opponent, game_id, GameMovement::Rock)?;
+			reveal_without_settling::<T>(&player, game_id, GameMovement::Paper)?;
+		}
+		let game_ids: BoundedVec<GameId, T::MaxBatch> = (0..GameId::from(n))
+			.collect::<vec::Vec<_>>()
+			.try_into()
+			.map_err(|_| "batch larger than MaxBatch")?;
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+	}: _(RawOrigin::Signed(caller), game_ids)
+	verify {
+		assert!((0..GameId::from(n)).all(is_settled::<T>));
+	}
+
+	claim_timeout {
+		game_with_opponent::<T>(1)?;
+		let caller = funded::<T>(whitelisted_caller());
+		join::<T>(&caller, 0, GameMovement::Paper)?;
+		reveal::<T>(&caller, 0, GameMovement::Paper)?;
+		let deadline = Games::<T>::get(0)
+			.and_then(|game_state| game_state.reveal_deadline)
+			.ok_or("game has no deadline")?;
+		frame_system::Pallet::<T>::set_block_number(deadline + One::one());
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(is_settled::<T>(0));
+	}
+
+	resolve_stalemate {
+		game_with_opponent::<T>(1)?;
+		let caller = funded::<T>(whitelisted_caller());
+		join::<T>(&caller, 0, GameMovement::Paper)?;
+		let deadline = Games::<T>::get(0)
+			.and_then(|game_state| game_state.reveal_deadline)
+			.ok_or("game has no deadline")?;
+		frame_system::Pallet::<T>::set_block_number(deadline + One::one());
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(is_settled::<T>(0));
+	}
+
+	claim_no_show {
+		let caller = funded::<T>(whitelisted_caller());
+		Template::<T>::do_create_game(&caller, GameMode::Classic, 1, false, None, None, None)?;
+		join::<T>(&caller, 0, GameMovement::Rock)?;
+		let deadline = Games::<T>::get(0)
+			.and_then(|game_state| game_state.join_deadline)
+			.ok_or("game has no join deadline")?;
+		frame_system::Pallet::<T>::set_block_number(deadline + One::one());
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(is_settled::<T>(0));
+	}
+
+	forfeit {
+		game_with_opponent::<T>(1)?;
+		let caller = funded::<T>(whitelisted_caller());
+		join::<T>(&caller, 0, GameMovement::Paper)?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(is_settled::<T>(0));
+	}
+
+	set_reveal_window {
+		let caller = funded::<T>(whitelisted_caller());
+		Template::<T>::do_create_game(&caller, GameMode::Classic, 1, false, None, None, None)?;
+	}: _(RawOrigin::Signed(caller), 0, false)
+	verify {
+		assert!(Games::<T>::get(0).map_or(false, |game_state| !game_state.reveal_window_open));
+	}
+
+	request_rematch {
+		let opponent = game_with_opponent::<T>(1)?;
+		let caller = funded::<T>(whitelisted_caller());
+		join::<T>(&caller, 0, GameMovement::Paper)?;
+		reveal::<T>(&opponent, 0, GameMovement::Rock)?;
+		reveal::<T>(&caller, 0, GameMovement::Paper)?;
+		Template::<T>::request_rematch(RawOrigin::Signed(opponent).into(), 0)?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert_eq!(Rematches::<T>::get(0), Some(1));
+	}
+
+	block_account {
+		let account: T::AccountId = account("banned", 0, SEED);
+	}: _(RawOrigin::Root, account.clone())
+	verify {
+		assert!(Blocked::<T>::contains_key(&account));
+	}
+
+	unblock_account {
+		let account: T::AccountId = account("banned", 0, SEED);
+		Blocked::<T>::insert(&account, ());
+	}: _(RawOrigin::Root, account.clone())
+	verify {
+		assert!(!Blocked::<T>::contains_key(&account));
+	}
+
+	cancel_inactive_game {
+		let caller = funded::<T>(whitelisted_caller());
+		Template::<T>::do_create_game(&caller, GameMode::Classic, 1, false, None, None, None)?;
+		join::<T>(&caller, 0, GameMovement::Rock)?;
+		let now = frame_system::Pallet::<T>::block_number() +
+			T::InactivityTimeout::get() +
+			One::one();
+	}: {
+		Template::<T>::on_idle(now, Weight::MAX);
+	}
+	verify {
+		assert!(!Games::<T>::contains_key(0));
+	}
+
+	purge_settled {
+		let n in 1 .. 100;
+		for game_id in 0..n {
+			Games::<T>::insert(
+				GameId::from(game_id),
+				GameState { status: GameStatus::Settled, ..Default::default() },
+			);
+		}
+	}: _(RawOrigin::Root, n)
+	verify {
+		assert_eq!(Games::<T>::iter().count(), 0);
+	}
+
+	set_min_accept {
+		let caller = funded::<T>(whitelisted_caller());
+		Template::<T>::do_create_game(&caller, GameMode::Classic, 1, false, None, None, None)?;
+	}: _(RawOrigin::Signed(caller), 0, bet::<T>())
+	verify {
+		assert_eq!(MinAccepts::<T>::get(0), Some(bet::<T>()));
+	}
+
+	transfer_ownership {
+		let caller = funded::<T>(whitelisted_caller());
+		let new_owner: T::AccountId = account("new_owner", 0, SEED);
+		Template::<T>::do_create_game(&caller, GameMode::Classic, 1, false, None, None, None)?;
+	}: _(RawOrigin::Signed(caller), 0, new_owner.clone())
+	verify {
+		assert_eq!(Games::<T>::get(0).unwrap().creator, Some(new_owner));
+	}
+
+	advance_season {}: _(RawOrigin::Root)
+	verify {
+		assert_eq!(CurrentSeason::<T>::get(), 1);
+	}
+
+	sponsor_game {
+		game_with_opponent::<T>(1)?;
+		join::<T>(&funded::<T>(account("player", 0, SEED)), 0, GameMovement::Paper)?;
+		let caller = funded::<T>(whitelisted_caller());
+	}: _(RawOrigin::Signed(caller.clone()), 0, bet::<T>())
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert_eq!(Sponsorships::<T>::get(0), Some((caller, bet::<T>())));
 	}
 
 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,1047 @@
+//! Types and pure game logic for rock-paper-scissors, independent of pallet storage.
+
+use frame_support::{pallet_prelude::*, sp_runtime::ArithmeticError, sp_std::vec::Vec};
+
+/// Bound of the metadata a game can be labelled with.
+pub type MaxMetadataLen = ConstU32<64>;
+
+/// Label given to a game by its creator, such as a game name or a tournament tag.
+pub type GameMetadata = BoundedVec<u8, MaxMetadataLen>;
+
+/// Identifier of an asset bets can be placed in.
+pub type AssetId = u32;
+
+/// Asset of the native currency of the chain.
+pub const NATIVE_ASSET: AssetId = 0;
+
+/// Identifier of a game.
+pub type GameId = u64;
+
+/// Period games are ranked in, started afresh by `advance_season`.
+pub type Season = u32;
+
+/// Value chosen by a player to hide their movement until it is revealed.
+///
+/// There are only three movements, so the secret is all that keeps a commitment from being
+/// brute forced: an opponent can hash every movement with every small secret and compare. Secrets
+/// below `Config::MinSecret` are therefore rejected at reveal time, as are secrets above
+/// `Config::MaxSecret`.
+pub type Secret = u64;
+
+/// A movement a player can make. `Lizard` and `Spock` are only allowed in
+/// `GameMode::LizardSpock` games.
+///
+/// Movements are ordered as declared, `Rock < Paper < Scissors < Lizard < Spock`, to give them a
+/// canonical order. The order says nothing about which movement beats which, see
+/// [`GameMovement::play`].
+#[derive(
+	Encode,
+	Decode,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(frame_support::Serialize, frame_support::Deserialize))]
+#[cfg_attr(feature = "std", serde(crate = "frame_support::serde"))]
+pub enum GameMovement {
+	Rock,
+	Paper,
+	Scissors,
+	Lizard,
+	Spock,
+}
+
+impl GameMovement {
+	/// Byte representation of the movement used when building commitments. Every variant needs
+	/// its own byte, or commitments to different movements would collide.
+	pub fn to_bytes(&self) -> u8 {
+		match self {
+			GameMovement::Rock => 1,
+			GameMovement::Paper => 2,
+			GameMovement::Scissors => 3,
+			GameMovement::Lizard => 4,
+			GameMovement::Spock => 5,
+		}
+	}
+
+	/// Plays `self` against `other`, returning the result from the point of view of `self`.
+	///
+	/// Never returns `GameResult::NotPlayed`. It is a pure function of the two movements, so
+	/// settling a round cannot read or change storage from within the rules.
+	pub fn play(&self, other: &GameMovement) -> GameResult {
+		match (self, other) {
+			(a, b) if a == b => GameResult::Draw,
+			(GameMovement::Rock, GameMovement::Scissors | GameMovement::Lizard) |
+			(GameMovement::Paper, GameMovement::Rock | GameMovement::Spock) |
+			(GameMovement::Scissors, GameMovement::Paper | GameMovement::Lizard) |
+			(GameMovement::Lizard, GameMovement::Spock | GameMovement::Paper) |
+			(GameMovement::Spock, GameMovement::Scissors | GameMovement::Rock) => GameResult::Win,
+			_ => GameResult::Lose,
+		}
+	}
+}
+
+impl GameMovement {
+	/// Name of the movement, as written and parsed by client tooling.
+	pub fn name(&self) -> &'static str {
+		match self {
+			GameMovement::Rock => "rock",
+			GameMovement::Paper => "paper",
+			GameMovement::Scissors => "scissors",
+			GameMovement::Lizard => "lizard",
+			GameMovement::Spock => "spock",
+		}
+	}
+}
+
+impl core::fmt::Display for GameMovement {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+/// Error returned when parsing a string that names no movement.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct ParseGameMovementError;
+
+impl core::fmt::Display for ParseGameMovementError {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		f.write_str("expected one of rock, paper, scissors, lizard or spock")
+	}
+}
+
+impl core::str::FromStr for GameMovement {
+	type Err = ParseGameMovementError;
+
+	/// Parses the name of a movement, ignoring case.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		[
+			GameMovement::Rock,
+			GameMovement::Paper,
+			GameMovement::Scissors,
+			GameMovement::Lizard,
+			GameMovement::Spock,
+		]
+		.into_iter()
+		.find(|movement| movement.name().eq_ignore_ascii_case(s))
+		.ok_or(ParseGameMovementError)
+	}
+}
+
+/// Outcome of a game, from the point of view of `player1`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(frame_support::Serialize, frame_support::Deserialize))]
+#[cfg_attr(feature = "std", serde(crate = "frame_support::serde"))]
+pub enum GameResult {
+	/// The game has not been settled yet.
+	NotPlayed,
+	/// `player1` won.
+	Win,
+	/// `player2` won.
+	Lose,
+	/// Nobody won.
+	Draw,
+}
+
+impl GameResult {
+	/// The same outcome from the point of view of `player2`.
+	pub fn inverse(&self) -> GameResult {
+		match self {
+			GameResult::Win => GameResult::Lose,
+			GameResult::Lose => GameResult::Win,
+			GameResult::Draw => GameResult::Draw,
+			GameResult::NotPlayed => GameResult::NotPlayed,
+		}
+	}
+
+	/// Player the outcome designates as the winner.
+	pub fn winner(&self) -> Winner {
+		match self {
+			GameResult::Win => Winner::Player1,
+			GameResult::Lose => Winner::Player2,
+			GameResult::Draw | GameResult::NotPlayed => Winner::Nobody,
+		}
+	}
+}
+
+impl Default for GameResult {
+	fn default() -> Self {
+		GameResult::NotPlayed
+	}
+}
+
+/// Slot a player holds in a game.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(frame_support::Serialize, frame_support::Deserialize))]
+#[cfg_attr(feature = "std", serde(crate = "frame_support::serde"))]
+pub enum PlayerSlot {
+	Player1,
+	Player2,
+}
+
+/// Slot of the player who won a game, regardless of who holds it.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(frame_support::Serialize, frame_support::Deserialize))]
+#[cfg_attr(feature = "std", serde(crate = "frame_support::serde"))]
+pub enum Winner {
+	Player1,
+	Player2,
+	/// The game is a draw or has not been settled yet.
+	Nobody,
+}
+
+/// Set of movements allowed in a game.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(frame_support::Serialize, frame_support::Deserialize))]
+#[cfg_attr(feature = "std", serde(crate = "frame_support::serde"))]
+pub enum GameMode {
+	/// Rock, paper and scissors.
+	Classic,
+	/// Rock, paper, scissors, lizard and Spock.
+	LizardSpock,
+}
+
+impl Default for GameMode {
+	fn default() -> Self {
+		GameMode::Classic
+	}
+}
+
+impl GameMode {
+	pub fn allows(&self, movement: &GameMovement) -> bool {
+		match self {
+			GameMode::Classic => matches!(
+				movement,
+				GameMovement::Rock | GameMovement::Paper | GameMovement::Scissors
+			),
+			GameMode::LizardSpock => true,
+		}
+	}
+}
+
+/// What happens to the stakes of a game that ends in a draw.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum DrawPolicy {
+	/// The last round is played again, up to `Config::MaxDrawReplays` times, whether or not the
+	/// game asked for it. Each stake is returned if the replays run out.
+	Replay,
+	/// Each player gets their stake back. Games created with `replay_on_draw` are still replayed
+	/// first.
+	SplitPot,
+	/// The whole pot goes to `Config::FeeDestination`. Games created with `replay_on_draw` are
+	/// still replayed first.
+	HouseKeeps,
+}
+
+impl Default for DrawPolicy {
+	fn default() -> Self {
+		DrawPolicy::SplitPot
+	}
+}
+
+/// Stage of a game's lifecycle.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(frame_support::Serialize, frame_support::Deserialize))]
+#[cfg_attr(feature = "std", serde(crate = "frame_support::serde"))]
+pub enum GameStatus {
+	/// Waiting for players to join.
+	Open,
+	/// Both players joined and have to commit to a movement for the next round.
+	AwaitingMoves,
+	/// Both players committed and their movements can be revealed.
+	AwaitingReveal,
+	/// The result is known.
+	Settled,
+}
+
+impl Default for GameStatus {
+	fn default() -> Self {
+		GameStatus::Open
+	}
+}
+
+/// Version of the commitment scheme used by games created from now on. Version 0 commitments are
+/// the `twox_64` hash of the preimage followed by zeroes, version 1 ones its `blake2_256` hash.
+pub const COMMITMENT_SCHEME_VERSION: u8 = 1;
+
+/// Preimage hashed into a [`SecretGameMovement`], for clients building commitments off-chain.
+///
+/// The layout is stable and any change to it will come with a new commitment scheme version. It is
+/// the SCALE encoding of, in order:
+/// - the movement byte from [`GameMovement::to_bytes`],
+/// - the secret as a little-endian `u64`,
+/// - the game id as a little-endian `u64`,
+/// - the SCALE encoding of the committing account, its raw 32 bytes for an `AccountId32`.
+pub fn commitment_bytes<AccountId: Encode>(
+	movement: &GameMovement,
+	secret: Secret,
+	game_id: GameId,
+	player: &AccountId,
+) -> Vec<u8> {
+	(movement.to_bytes(), secret, game_id, player).encode()
+}
+
+/// Commitment to a movement: the hash of the movement and a player chosen secret, bound to the
+/// game and the committing player so it cannot be replayed elsewhere. See [`commitment_bytes`]
+/// for the exact preimage and [`COMMITMENT_SCHEME_VERSION`] for the hash.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(frame_support::Serialize, frame_support::Deserialize))]
+#[cfg_attr(feature = "std", serde(crate = "frame_support::serde"))]
+pub struct SecretGameMovement([u8; 32]);
+
+impl SecretGameMovement {
+	/// Commits `player` to `movement` in `game_id`, hiding it behind `secret`, under the current
+	/// commitment scheme.
+	pub fn new<AccountId: Encode>(
+		movement: &GameMovement,
+		secret: Secret,
+		game_id: GameId,
+		player: &AccountId,
+	) -> Self {
+		Self(sp_io::hashing::blake2_256(&commitment_bytes(movement, secret, game_id, player)))
+	}
+
+	/// Commits `player` to `movement` in `game_id` under the given commitment scheme version,
+	/// `None` if the version is unknown.
+	pub fn with_scheme<AccountId: Encode>(
+		scheme_version: u8,
+		movement: &GameMovement,
+		secret: Secret,
+		game_id: GameId,
+		player: &AccountId,
+	) -> Option<Self> {
+		match scheme_version {
+			0 => {
+				let mut hash = [0; 32];
+				hash[..8].copy_from_slice(&sp_io::hashing::twox_64(&commitment_bytes(
+					movement, secret, game_id, player,
+				)));
+				Some(Self(hash))
+			},
+			1 => Some(Self::new(movement, secret, game_id, player)),
+			_ => None,
+		}
+	}
+
+	/// Checks whether this commitment was built from `movement` and `secret` by `player` for
+	/// `game_id` under the current commitment scheme.
+	pub fn is_equal<AccountId: Encode>(
+		&self,
+		movement: &GameMovement,
+		secret: Secret,
+		game_id: GameId,
+		player: &AccountId,
+	) -> bool {
+		self.is_equal_with_scheme(COMMITMENT_SCHEME_VERSION, movement, secret, game_id, player)
+	}
+
+	/// Checks whether this commitment was built from `movement` and `secret` by `player` for
+	/// `game_id` under the given commitment scheme version.
+	pub fn is_equal_with_scheme<AccountId: Encode>(
+		&self,
+		scheme_version: u8,
+		movement: &GameMovement,
+		secret: Secret,
+		game_id: GameId,
+		player: &AccountId,
+	) -> bool {
+		Self::with_scheme(scheme_version, movement, secret, game_id, player).as_ref() == Some(self)
+	}
+}
+
+/// A player taking part in a game, with their committed and, once revealed, plain movement for
+/// the current round.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(frame_support::Serialize, frame_support::Deserialize))]
+#[cfg_attr(feature = "std", serde(crate = "frame_support::serde"))]
+pub struct PlayerMovement<AccountId, Balance, BlockNumber> {
+	pub player: AccountId,
+	pub secret_movement: Option<SecretGameMovement>,
+	pub movement: Option<GameMovement>,
+	/// Secret the movement was revealed with, kept to check it against the commitment again when
+	/// the round is settled.
+	pub secret: Option<Secret>,
+	/// Amount reserved from the player when they joined.
+	pub bet: Balance,
+	/// Block in which the player last committed to a movement.
+	pub committed_at: BlockNumber,
+}
+
+impl<AccountId, Balance, BlockNumber> PlayerMovement<AccountId, Balance, BlockNumber> {
+	pub fn new(
+		player: AccountId,
+		secret_movement: SecretGameMovement,
+		bet: Balance,
+		committed_at: BlockNumber,
+	) -> Self {
+		Self {
+			player,
+			secret_movement: Some(secret_movement),
+			movement: None,
+			secret: None,
+			bet,
+			committed_at,
+		}
+	}
+
+	pub fn has_committed(&self) -> bool {
+		self.secret_movement.is_some()
+	}
+
+	pub fn is_revealed(&self) -> bool {
+		self.movement.is_some()
+	}
+
+	/// Records the movement the player revealed along with its secret.
+	pub fn reveal(&mut self, movement: GameMovement, secret: Secret) {
+		self.movement = Some(movement);
+		self.secret = Some(secret);
+	}
+
+	/// Clears the commitment and movement so the player can commit again in the next round.
+	pub fn reset_movement(&mut self) {
+		self.secret_movement = None;
+		self.movement = None;
+		self.secret = None;
+	}
+}
+
+impl<AccountId: Encode, Balance, BlockNumber> PlayerMovement<AccountId, Balance, BlockNumber> {
+	/// Checks whether the player committed to `movement` with `secret` in `game_id` under the
+	/// given commitment scheme version.
+	pub fn is_commitment_of(
+		&self,
+		movement: &GameMovement,
+		secret: Secret,
+		game_id: GameId,
+		scheme_version: u8,
+	) -> bool {
+		self.secret_movement.as_ref().map_or(false, |secret_movement| {
+			secret_movement.is_equal_with_scheme(
+				scheme_version,
+				movement,
+				secret,
+				game_id,
+				&self.player,
+			)
+		})
+	}
+
+	/// Checks whether the recorded movement and secret still match the commitment, `false`
+	/// before the player revealed.
+	pub fn reveal_matches_commitment(&self, game_id: GameId, scheme_version: u8) -> bool {
+		match (self.movement, self.secret) {
+			(Some(movement), Some(secret)) =>
+				self.is_commitment_of(&movement, secret, game_id, scheme_version),
+			_ => false,
+		}
+	}
+}
+
+/// State of a game. `player1` is the first player to join and results are expressed from their
+/// point of view. `MaxRounds` bounds the number of rounds a game is played over.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(frame_support::Serialize, frame_support::Deserialize))]
+#[cfg_attr(feature = "std", serde(crate = "frame_support::serde"))]
+#[scale_info(skip_type_params(MaxRounds))]
+#[codec(mel_bound(
+	AccountId: MaxEncodedLen,
+	Balance: MaxEncodedLen,
+	BlockNumber: MaxEncodedLen
+))]
+pub struct GameState<AccountId, Balance, BlockNumber, MaxRounds: Get<u32>> {
+	pub mode: GameMode,
+	/// Number of rounds the game is played over, always odd.
+	pub rounds: u8,
+	/// Number of rounds settled so far, draws included.
+	pub rounds_played: u8,
+	/// Rounds won by `player1`.
+	pub player1_score: u8,
+	/// Rounds won by `player2`.
+	pub player2_score: u8,
+	/// Whether a game ending in a draw is replayed instead of settled, up to
+	/// `Config::MaxDrawReplays` times.
+	pub replay_on_draw: bool,
+	/// Number of times the last round was replayed after a draw.
+	pub draw_replays: u8,
+	pub status: GameStatus,
+	pub player1: Option<PlayerMovement<AccountId, Balance, BlockNumber>>,
+	pub player2: Option<PlayerMovement<AccountId, Balance, BlockNumber>>,
+	pub game_result: GameResult,
+	pub winner: Option<AccountId>,
+	/// Account that created the game, `None` for games created at genesis.
+	pub creator: Option<AccountId>,
+	/// Only account allowed to play against the first player, if the game is private.
+	pub opponent: Option<AccountId>,
+	/// Bet every player has to place, set by the first player to join.
+	pub bet: Option<Balance>,
+	/// Sum of the bets placed in the game, zeroed once it is paid out.
+	pub pot: Balance,
+	/// Number of blocks players have in each phase of the game once both joined,
+	/// `Config::RevealTimeout` if `None`. Open games always expire after `Config::RevealTimeout`.
+	pub reveal_timeout: Option<BlockNumber>,
+	/// Last block in which players can act in the current phase. An open game expires after it,
+	/// while a round in play can then be claimed by the player who acted. Set when the game is
+	/// created, once both players joined and whenever the round moves on.
+	pub reveal_deadline: Option<BlockNumber>,
+	/// Last block in which a second player can join before the first one can claim the game as
+	/// won by no-show. Set when the game is created.
+	pub join_deadline: Option<BlockNumber>,
+	/// Whether players can reveal their movements. The creator can close it to collect
+	/// commitments over many blocks, as in tournaments, and open it once everybody should reveal.
+	pub reveal_window_open: bool,
+	/// Block in which the game was created, the genesis block for games created at genesis.
+	pub created_at: BlockNumber,
+	/// Version of the commitment scheme the players of the game commit with, see
+	/// [`COMMITMENT_SCHEME_VERSION`].
+	pub scheme_version: u8,
+	/// Result of each round settled so far, from the point of view of `player1`. A drawn round
+	/// that is replayed is dropped from it, so it holds `rounds_played` results.
+	#[cfg_attr(feature = "std", serde(bound = ""))]
+	pub round_results: BoundedVec<GameResult, MaxRounds>,
+	/// Asset every player bets in, set by the first player to join along with `bet`.
+	pub asset_id: AssetId,
+	/// Label given to the game by its creator, if any.
+	pub metadata: Option<GameMetadata>,
+}
+
+impl<AccountId, Balance: Default, BlockNumber: Default, MaxRounds: Get<u32>> Default
+	for GameState<AccountId, Balance, BlockNumber, MaxRounds>
+{
+	fn default() -> Self {
+		Self {
+			mode: GameMode::default(),
+			rounds: 1,
+			rounds_played: 0,
+			player1_score: 0,
+			player2_score: 0,
+			replay_on_draw: false,
+			draw_replays: 0,
+			status: GameStatus::default(),
+			player1: None,
+			player2: None,
+			game_result: GameResult::default(),
+			winner: None,
+			creator: None,
+			opponent: None,
+			bet: None,
+			pot: Balance::default(),
+			reveal_timeout: None,
+			reveal_deadline: None,
+			join_deadline: None,
+			reveal_window_open: true,
+			created_at: BlockNumber::default(),
+			scheme_version: COMMITMENT_SCHEME_VERSION,
+			round_results: BoundedVec::default(),
+			asset_id: NATIVE_ASSET,
+			metadata: None,
+		}
+	}
+}
+
+impl<AccountId: PartialEq, Balance, BlockNumber, MaxRounds: Get<u32>>
+	GameState<AccountId, Balance, BlockNumber, MaxRounds>
+{
+	/// Number of players that can still join the game.
+	pub fn free_slots(&self) -> u8 {
+		[&self.player1, &self.player2].iter().filter(|slot| slot.is_none()).count() as u8
+	}
+
+	pub fn has_free_slots(&self) -> bool {
+		self.free_slots() > 0
+	}
+
+	pub fn has_player(&self, who: &AccountId) -> bool {
+		self.player_movement(who).is_some()
+	}
+
+	pub fn is_finished(&self) -> bool {
+		self.status == GameStatus::Settled
+	}
+
+	/// Counts the result of a settled round towards the score and adds it to the round history.
+	///
+	/// No game is played over more than `max_rounds` rounds, so recording a round past that, which
+	/// only corrupted storage can lead to, fails with an overflow and leaves the game untouched.
+	pub fn record_round(
+		&mut self,
+		round_result: &GameResult,
+		max_rounds: u8,
+	) -> Result<(), ArithmeticError> {
+		let checked_inc = |count: u8| {
+			count
+				.checked_add(1)
+				.filter(|count| *count <= max_rounds)
+				.ok_or(ArithmeticError::Overflow)
+		};
+		let rounds_played = checked_inc(self.rounds_played)?;
+		let (player1_score, player2_score) = match round_result {
+			GameResult::Win => (checked_inc(self.player1_score)?, self.player2_score),
+			GameResult::Lose => (self.player1_score, checked_inc(self.player2_score)?),
+			GameResult::Draw | GameResult::NotPlayed => (self.player1_score, self.player2_score),
+		};
+		// The history is bounded by `MaxRounds`, which `max_rounds` never exceeds, so it cannot be
+		// full.
+		let _ = self.round_results.try_push(round_result.clone());
+		self.rounds_played = rounds_played;
+		self.player1_score = player1_score;
+		self.player2_score = player2_score;
+		Ok(())
+	}
+
+	/// Results of the rounds settled so far, in the order they were played.
+	pub fn round_history(&self) -> &[GameResult] {
+		&self.round_results
+	}
+
+	/// Player who won the given round, counting from 0. `None` if the round was drawn or not
+	/// played yet.
+	pub fn winner_of_round(&self, round: u8) -> Option<&AccountId> {
+		let player_movement = match self.round_results.get(round as usize)? {
+			GameResult::Win => &self.player1,
+			GameResult::Lose => &self.player2,
+			GameResult::Draw | GameResult::NotPlayed => return None,
+		};
+		player_movement.as_ref().map(|player_movement| &player_movement.player)
+	}
+
+	/// Whether both players joined and revealed their movement for the current round, so the
+	/// round can be settled.
+	pub fn ready_to_settle(&self) -> bool {
+		match (&self.player1, &self.player2) {
+			(Some(player1), Some(player2)) => player1.is_revealed() && player2.is_revealed(),
+			_ => false,
+		}
+	}
+
+	/// Result of the game once no remaining round can change it, `None` before that.
+	pub fn decided_result(&self) -> Option<GameResult> {
+		let remaining = self.rounds.saturating_sub(self.rounds_played);
+		let (score1, score2) = (self.player1_score, self.player2_score);
+		if remaining > 0 &&
+			score1 <= score2.saturating_add(remaining) &&
+			score2 <= score1.saturating_add(remaining)
+		{
+			return None
+		}
+		Some(match score1.cmp(&score2) {
+			core::cmp::Ordering::Greater => GameResult::Win,
+			core::cmp::Ordering::Less => GameResult::Lose,
+			core::cmp::Ordering::Equal => GameResult::Draw,
+		})
+	}
+
+	/// Clears both players' movements so they can commit to the next round.
+	pub fn start_next_round(&mut self) {
+		for player_movement in [&mut self.player1, &mut self.player2].into_iter().flatten() {
+			player_movement.reset_movement();
+		}
+		self.status = GameStatus::AwaitingMoves;
+	}
+
+	/// Plays the last round again after it left the game drawn, with the same players and bets.
+	pub fn replay_last_round(&mut self) {
+		if let Some(last) = self.round_results.len().checked_sub(1) {
+			self.round_results.remove(last);
+		}
+		self.rounds_played = self.rounds_played.saturating_sub(1);
+		self.draw_replays = self.draw_replays.saturating_add(1);
+		self.start_next_round();
+	}
+
+	/// Whether `who` may join the game. Only its creator and the invited opponent may take the
+	/// slots of a private game.
+	pub fn admits(&self, who: &AccountId) -> bool {
+		match &self.opponent {
+			Some(opponent) => who == opponent || self.creator.as_ref() == Some(who),
+			None => true,
+		}
+	}
+
+	/// Seats `player_movement` in the first free slot and returns that slot, `None` if the game
+	/// is full.
+	pub fn add_player(
+		&mut self,
+		player_movement: PlayerMovement<AccountId, Balance, BlockNumber>,
+	) -> Option<PlayerSlot> {
+		if self.player1.is_none() {
+			self.player1 = Some(player_movement);
+			Some(PlayerSlot::Player1)
+		} else if self.player2.is_none() {
+			self.player2 = Some(player_movement);
+			Some(PlayerSlot::Player2)
+		} else {
+			None
+		}
+	}
+
+	pub fn player_movement(
+		&self,
+		who: &AccountId,
+	) -> Option<&PlayerMovement<AccountId, Balance, BlockNumber>> {
+		[&self.player1, &self.player2].into_iter().flatten().find(|p| p.player == *who)
+	}
+
+	pub fn player_movement_mut(
+		&mut self,
+		who: &AccountId,
+	) -> Option<&mut PlayerMovement<AccountId, Balance, BlockNumber>> {
+		[&mut self.player1, &mut self.player2]
+			.into_iter()
+			.flatten()
+			.find(|p| p.player == *who)
+	}
+}
+
+/// Everything a front-end needs to display a game, as returned by the runtime API.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(MaxRounds))]
+pub struct GameDetails<AccountId, Balance, BlockNumber, MaxRounds: Get<u32>> {
+	pub game_state: GameState<AccountId, Balance, BlockNumber, MaxRounds>,
+	pub status: GameStatus,
+	/// Blocks left before the current deadline passes, `None` once the game is settled.
+	pub blocks_until_timeout: Option<BlockNumber>,
+	/// Sum of the bets at stake in the game.
+	pub pot: Balance,
+}
+
+/// Outcome of a settled game, kept after the game itself is purged for historical queries.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct GameOutcome<AccountId, Balance, BlockNumber> {
+	/// First player of the game with the movement they revealed in the last round, if any, and
+	/// their bet.
+	pub player1: Option<(AccountId, Option<GameMovement>, Balance)>,
+	/// Second player of the game, as for `player1`.
+	pub player2: Option<(AccountId, Option<GameMovement>, Balance)>,
+	pub game_result: GameResult,
+	pub winner: Option<AccountId>,
+	/// House fee taken from the bets.
+	pub fee: Balance,
+	/// Block in which the game was settled.
+	pub settled_at: BlockNumber,
+	/// Season in which the game was settled.
+	pub season: Season,
+}
+
+/// Games of a player sorted by what they have to do about them, for wallets to show at once.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, Default)]
+pub struct PlayerDashboard {
+	/// Games where the player has to commit to or reveal their movement for the round.
+	pub to_reveal: Vec<GameId>,
+	/// Games where the player waits for an opponent to join or to act.
+	pub waiting_opponent: Vec<GameId>,
+	/// Games the player can settle or claim with `settle_game`, `claim_timeout` or
+	/// `claim_no_show`.
+	pub claimable: Vec<GameId>,
+	/// Settled games of the player whose outcome is still kept.
+	pub finished: Vec<GameId>,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::BTreeSet;
+
+	const MOVEMENTS: [GameMovement; 3] =
+		[GameMovement::Rock, GameMovement::Paper, GameMovement::Scissors];
+
+	#[test]
+	fn should_play_every_lizard_spock_matchup() {
+		use GameMovement::*;
+		use GameResult::*;
+
+		let all = [Rock, Paper, Scissors, Lizard, Spock];
+		// `expected[i][j]` is the result of `all[i]` played against `all[j]`.
+		let expected = [
+			[Draw, Lose, Win, Win, Lose],
+			[Win, Draw, Lose, Lose, Win],
+			[Lose, Win, Draw, Win, Lose],
+			[Lose, Win, Lose, Draw, Win],
+			[Win, Lose, Win, Lose, Draw],
+		];
+		for (i, movement) in all.iter().enumerate() {
+			for (j, other) in all.iter().enumerate() {
+				assert_eq!(movement.play(other), expected[i][j], "{:?} vs {:?}", movement, other);
+			}
+		}
+	}
+
+	#[test]
+	fn movements_are_ordered_as_declared() {
+		use GameMovement::*;
+
+		assert!(Rock < Paper && Paper < Scissors && Scissors < Lizard && Lizard < Spock);
+		// Scissors beats Paper and loses to Rock whatever their order.
+		assert_eq!(Scissors.play(&Paper), GameResult::Win);
+		assert_eq!(Scissors.play(&Rock), GameResult::Lose);
+
+		let mut movements = vec![Spock, Scissors, Rock, Paper, Rock, Lizard, Scissors];
+		movements.sort();
+		assert_eq!(movements, vec![Rock, Rock, Paper, Scissors, Scissors, Lizard, Spock]);
+		let sorted = movements.clone();
+		movements.sort();
+		assert_eq!(movements, sorted);
+	}
+
+	#[test]
+	fn inverse_result_swaps_the_winner() {
+		assert_eq!(GameResult::Win.inverse(), GameResult::Lose);
+		assert_eq!(GameResult::Lose.inverse(), GameResult::Win);
+		assert_eq!(GameResult::Draw.inverse(), GameResult::Draw);
+		assert_eq!(GameResult::NotPlayed.inverse(), GameResult::NotPlayed);
+	}
+
+	#[test]
+	fn inverse_result_matches_swapped_movements() {
+		let all = [
+			GameMovement::Rock,
+			GameMovement::Paper,
+			GameMovement::Scissors,
+			GameMovement::Lizard,
+			GameMovement::Spock,
+		];
+		for movement in all.iter() {
+			for other in all.iter() {
+				assert_eq!(movement.play(other).inverse(), other.play(movement));
+			}
+		}
+	}
+
+	#[test]
+	fn classic_mode_only_allows_classic_movements() {
+		assert!(MOVEMENTS.iter().all(|movement| GameMode::Classic.allows(movement)));
+		assert!(!GameMode::Classic.allows(&GameMovement::Lizard));
+		assert!(!GameMode::Classic.allows(&GameMovement::Spock));
+		assert!(GameMode::LizardSpock.allows(&GameMovement::Lizard));
+		assert!(GameMode::LizardSpock.allows(&GameMovement::Spock));
+	}
+
+	#[test]
+	fn secret_movement_verifies_its_preimage() {
+		let secret_movement = SecretGameMovement::new(&GameMovement::Paper, 42, 0, &1u64);
+		assert!(secret_movement.is_equal(&GameMovement::Paper, 42, 0, &1u64));
+		assert!(!secret_movement.is_equal(&GameMovement::Paper, 43, 0, &1u64));
+		assert!(!secret_movement.is_equal(&GameMovement::Rock, 42, 0, &1u64));
+	}
+
+	#[test]
+	fn commitment_matches_pinned_hash() {
+		let preimage = commitment_bytes(&GameMovement::Rock, 1234, 0, &1u64);
+		assert_eq!(
+			preimage,
+			[[1u8].as_slice(), &1234u64.to_le_bytes(), &0u64.to_le_bytes(), &1u64.to_le_bytes()]
+				.concat()
+		);
+		assert_eq!(
+			SecretGameMovement::new(&GameMovement::Rock, 1234, 0, &1u64).0,
+			[
+				0xe5, 0x6a, 0xb2, 0xc3, 0x64, 0x87, 0x93, 0xa1, 0x08, 0xa6, 0x6a, 0x85, 0xcd, 0xe3,
+				0x1d, 0xae, 0xd7, 0x80, 0x5b, 0x0b, 0x7e, 0xc8, 0x59, 0x22, 0xc6, 0x94, 0x09, 0x32,
+				0xf6, 0x22, 0x13, 0x4b,
+			]
+		);
+	}
+
+	#[test]
+	fn commitments_verify_under_their_own_scheme() {
+		let legacy =
+			SecretGameMovement::with_scheme(0, &GameMovement::Paper, 42, 0, &1u64).unwrap();
+		let current =
+			SecretGameMovement::with_scheme(1, &GameMovement::Paper, 42, 0, &1u64).unwrap();
+		assert_eq!(current, SecretGameMovement::new(&GameMovement::Paper, 42, 0, &1u64));
+		assert!(legacy.0[8..].iter().all(|byte| *byte == 0));
+
+		assert!(legacy.is_equal_with_scheme(0, &GameMovement::Paper, 42, 0, &1u64));
+		assert!(!legacy.is_equal_with_scheme(0, &GameMovement::Rock, 42, 0, &1u64));
+		assert!(!legacy.is_equal_with_scheme(1, &GameMovement::Paper, 42, 0, &1u64));
+		assert!(current.is_equal_with_scheme(1, &GameMovement::Paper, 42, 0, &1u64));
+		assert!(!current.is_equal_with_scheme(0, &GameMovement::Paper, 42, 0, &1u64));
+		assert!(!current.is_equal_with_scheme(2, &GameMovement::Paper, 42, 0, &1u64));
+		assert_eq!(SecretGameMovement::with_scheme(2, &GameMovement::Paper, 42, 0, &1u64), None);
+	}
+
+	#[test]
+	fn secret_movements_do_not_collide() {
+		let commitments: BTreeSet<_> = MOVEMENTS
+			.iter()
+			.flat_map(|movement| {
+				(0..10_000).map(move |secret| SecretGameMovement::new(movement, secret, 0, &1u64).0)
+			})
+			.collect();
+		assert_eq!(commitments.len(), MOVEMENTS.len() * 10_000);
+	}
+
+	#[test]
+	fn secret_movement_is_bound_to_game_and_player() {
+		let secret_movement = SecretGameMovement::new(&GameMovement::Rock, 42, 1, &1u64);
+		assert!(secret_movement.is_equal(&GameMovement::Rock, 42, 1, &1u64));
+		assert!(!secret_movement.is_equal(&GameMovement::Rock, 42, 2, &1u64));
+		assert!(!secret_movement.is_equal(&GameMovement::Rock, 42, 1, &2u64));
+	}
+
+	#[derive(Clone, PartialEq, Eq, Debug)]
+	struct MaxTestRounds;
+
+	impl Get<u32> for MaxTestRounds {
+		fn get() -> u32 {
+			u8::MAX.into()
+		}
+	}
+
+	type TestGameState = GameState<u64, u64, u64, MaxTestRounds>;
+
+	#[test]
+	fn game_is_ready_to_settle_once_both_players_revealed() {
+		let player = |who: u64| {
+			let secret_movement = SecretGameMovement::new(&GameMovement::Rock, 42, 0, &who);
+			PlayerMovement::new(who, secret_movement, 100u64, 1u64)
+		};
+		let mut game_state = TestGameState::default();
+		assert!(!game_state.ready_to_settle());
+
+		let mut player1 = player(1);
+		player1.movement = Some(GameMovement::Rock);
+		game_state.player1 = Some(player1);
+		assert!(!game_state.ready_to_settle());
+
+		game_state.player2 = Some(player(2));
+		assert!(!game_state.ready_to_settle());
+
+		game_state.player2.as_mut().unwrap().movement = Some(GameMovement::Rock);
+		assert!(game_state.ready_to_settle());
+
+		game_state.player1.as_mut().unwrap().reset_movement();
+		assert!(!game_state.ready_to_settle());
+	}
+
+	#[test]
+	fn round_scores_stop_at_the_round_bound() {
+		let mut game_state = TestGameState { rounds: 3, ..Default::default() };
+		for _ in 0..3 {
+			assert_eq!(game_state.record_round(&GameResult::Win, 3), Ok(()));
+		}
+		assert_eq!((game_state.rounds_played, game_state.player1_score), (3, 3));
+
+		let recorded = game_state.clone();
+		assert_eq!(game_state.record_round(&GameResult::Draw, 3), Err(ArithmeticError::Overflow));
+		assert_eq!(game_state.record_round(&GameResult::Lose, 3), Err(ArithmeticError::Overflow));
+		assert_eq!(game_state, recorded);
+
+		let mut game_state = TestGameState {
+			rounds_played: u8::MAX - 1,
+			player2_score: u8::MAX - 1,
+			..Default::default()
+		};
+		assert_eq!(game_state.record_round(&GameResult::Lose, u8::MAX), Ok(()));
+		assert_eq!(game_state.player2_score, u8::MAX);
+		assert_eq!(
+			game_state.record_round(&GameResult::Lose, u8::MAX),
+			Err(ArithmeticError::Overflow)
+		);
+		assert_eq!(game_state.player2_score, u8::MAX);
+	}
+
+	#[test]
+	fn players_are_seated_in_the_first_free_slot() {
+		let player = |who: u64| {
+			let secret_movement = SecretGameMovement::new(&GameMovement::Rock, 42, 0, &who);
+			PlayerMovement::new(who, secret_movement, 100u64, 1u64)
+		};
+		let mut game_state = TestGameState::default();
+
+		assert_eq!(game_state.add_player(player(1)), Some(PlayerSlot::Player1));
+		assert_eq!(game_state.player1.as_ref().map(|p| p.player), Some(1));
+		assert_eq!(game_state.add_player(player(2)), Some(PlayerSlot::Player2));
+		assert_eq!(game_state.player2.as_ref().map(|p| p.player), Some(2));
+
+		assert_eq!(game_state.add_player(player(3)), None);
+		assert_eq!(game_state.player1.as_ref().map(|p| p.player), Some(1));
+		assert_eq!(game_state.player2.as_ref().map(|p| p.player), Some(2));
+	}
+
+	#[test]
+	fn free_slots_count_the_seats_left() {
+		let player = |who: u64| {
+			let secret_movement = SecretGameMovement::new(&GameMovement::Rock, 42, 0, &who);
+			PlayerMovement::new(who, secret_movement, 100u64, 1u64)
+		};
+		let mut game_state = TestGameState::default();
+		assert_eq!(game_state.free_slots(), 2);
+		assert!(game_state.has_free_slots());
+
+		game_state.add_player(player(1));
+		assert_eq!(game_state.free_slots(), 1);
+		assert!(game_state.has_free_slots());
+
+		game_state.add_player(player(2));
+		assert_eq!(game_state.free_slots(), 0);
+		assert!(!game_state.has_free_slots());
+	}
+
+	#[test]
+	fn movements_round_trip_through_their_names() {
+		use GameMovement::*;
+
+		for movement in [Rock, Paper, Scissors, Lizard, Spock] {
+			assert_eq!(movement.to_string().parse(), Ok(movement));
+		}
+		assert_eq!("ROCK".parse(), Ok(Rock));
+		assert_eq!("Spock".parse(), Ok(Spock));
+	}
+
+	#[test]
+	fn unknown_movement_names_do_not_parse() {
+		assert_eq!("well".parse::<GameMovement>(), Err(ParseGameMovementError));
+		assert_eq!("".parse::<GameMovement>(), Err(ParseGameMovementError));
+	}
+
+	#[test]
+	fn every_movement_has_its_own_byte() {
+		use GameMovement::*;
+
+		let all = [Rock, Paper, Scissors, Lizard, Spock];
+		for movement in all {
+			// Adding a variant breaks this match, as a reminder to list it above.
+			match movement {
+				Rock | Paper | Scissors | Lizard | Spock => (),
+			}
+		}
+		let bytes: BTreeSet<u8> = all.iter().map(GameMovement::to_bytes).collect();
+		assert_eq!(bytes.len(), all.len());
+	}
+
+	#[test]
+	fn game_state_round_trips_through_json() {
+		let commit = |movement, secret, player: u64| {
+			PlayerMovement::new(
+				player,
+				SecretGameMovement::new(&movement, secret, 0, &player),
+				100u64,
+				2u64,
+			)
+		};
+		let mut player1 = commit(GameMovement::Spock, 1_000, 1);
+		player1.reveal(GameMovement::Spock, 1_000);
+		let player2 = commit(GameMovement::Rock, 2_000, 2);
+		let game_state = TestGameState {
+			mode: GameMode::LizardSpock,
+			status: GameStatus::AwaitingReveal,
+			player1: Some(player1),
+			player2: Some(player2),
+			creator: Some(1),
+			bet: Some(100),
+			pot: 200,
+			reveal_deadline: Some(13),
+			..Default::default()
+		};
+
+		let json = serde_json::to_string(&game_state).unwrap();
+		assert_eq!(serde_json::from_str::<TestGameState>(&json).unwrap(), game_state);
+	}
+}
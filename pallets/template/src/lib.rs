@@ -1,10 +1,18 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-/// Edit this file to define custom logic or remove it if it is not needed.
+/// A rock-paper-scissors game where players commit to a hidden movement and reveal it once
+/// both have played.
 /// Learn more about FRAME and the core library of Substrate FRAME pallets:
 /// <https://docs.substrate.io/v3/runtime/frame>
 pub use pallet::*;
 
+pub mod game_logic;
+pub mod migrations;
+pub mod multi_currency;
+pub mod runtime_api;
+pub mod weights;
+pub use weights::WeightInfo;
+
 #[cfg(test)]
 mod mock;
 
@@ -16,87 +24,1980 @@ mod benchmarking;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::pallet_prelude::*;
+	use crate::{game_logic::*, multi_currency::MultiReservableCurrency, weights::WeightInfo};
+	use frame_support::{
+		dispatch::WithPostDispatchInfo,
+		pallet_prelude::*,
+		sp_runtime::{
+			traits::{Saturating, Zero},
+			Permill, TokenError,
+		},
+		sp_std::vec::Vec,
+		traits::{Currency, ExistenceRequirement, WithdrawReasons},
+		transactional,
+	};
 	use frame_system::pallet_prelude::*;
 
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type PlayerMovementOf<T> = PlayerMovement<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+	pub type GameStateOf<T> = GameState<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+		MaxRoundsOf<T>,
+	>;
+	pub type GameOutcomeOf<T> = GameOutcome<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+	pub type GameDetailsOf<T> = GameDetails<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+		MaxRoundsOf<T>,
+	>;
+
+	/// `Config::MaxRounds` as a `u32`, bounding the round history of the games of the pallet.
+	#[derive(CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound)]
+	pub struct MaxRoundsOf<T>(PhantomData<T>);
+
+	impl<T: Config> Get<u32> for MaxRoundsOf<T> {
+		fn get() -> u32 {
+			T::MaxRounds::get().into()
+		}
+	}
+
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for the extrinsics of this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Native currency, in which the game creation fee is paid.
+		type Currency: Currency<Self::AccountId>;
+
+		/// Assets in which bets are placed, with the balance type of the native currency.
+		type Assets: MultiReservableCurrency<Self::AccountId, Balance = BalanceOf<Self>>;
+
+		/// Share of the loser's bet taken by the house when a game has a winner.
+		#[pallet::constant]
+		type HouseFee: Get<Permill>;
+
+		/// Account receiving the house fees.
+		type FeeDestination: Get<Self::AccountId>;
+
+		/// Amount burnt from the creator of every game, independently of the bets.
+		#[pallet::constant]
+		type GameCreationFee: Get<BalanceOf<Self>>;
+
+		/// Smallest bet a player can join a game with. Games can only be played for free when it
+		/// is zero.
+		#[pallet::constant]
+		type MinBet: Get<BalanceOf<Self>>;
+
+		/// Largest bet a player can join a game with.
+		#[pallet::constant]
+		type MaxBet: Get<BalanceOf<Self>>;
+
+		/// Smallest secret accepted when revealing a movement.
+		#[pallet::constant]
+		type MinSecret: Get<Secret>;
+
+		/// Largest secret accepted when revealing a movement, so deployments can keep secrets in a
+		/// narrower range than `Secret` allows.
+		#[pallet::constant]
+		type MaxSecret: Get<Secret>;
+
+		/// Largest number of rounds a game can be played over.
+		#[pallet::constant]
+		type MaxRounds: Get<u8>;
+
+		/// Number of blocks players have to commit to or reveal their movements in each round,
+		/// unless the creator of the game chose another one.
+		#[pallet::constant]
+		type RevealTimeout: Get<Self::BlockNumber>;
+
+		/// Shortest reveal timeout the creator of a game can choose.
+		#[pallet::constant]
+		type MinTimeout: Get<Self::BlockNumber>;
+
+		/// Longest reveal timeout the creator of a game can choose.
+		#[pallet::constant]
+		type MaxTimeout: Get<Self::BlockNumber>;
+
+		/// Number of blocks a second player has to join a game before the first one can claim it
+		/// as won by no-show.
+		#[pallet::constant]
+		type JoinTimeout: Get<Self::BlockNumber>;
+
+		/// Largest number of unsettled games a player can take part in at once.
+		#[pallet::constant]
+		type MaxGamesPerPlayer: Get<u32>;
+
+		/// Largest number of times the last round of a drawn game is replayed.
+		#[pallet::constant]
+		type MaxDrawReplays: Get<u8>;
+
+		/// What happens to the stakes of a game that ends in a draw.
+		#[pallet::constant]
+		type DrawPolicy: Get<DrawPolicy>;
+
+		/// Whether games can end in a draw. When `false`, every drawn game is replayed up to
+		/// `MaxDrawReplays` times and only then settled according to `DrawPolicy`.
+		#[pallet::constant]
+		type AllowDraws: Get<bool>;
+
+		/// Largest number of unsettled games that can exist at once.
+		#[pallet::constant]
+		type MaxOpenGames: Get<u32>;
+
+		/// Largest number of games examined for expiry at the start of each block.
+		#[pallet::constant]
+		type MaxExpirySweep: Get<u32>;
+
+		/// Number of blocks after which a game still open is cancelled and its bet returned
+		/// whenever a block has weight to spare, even before its deadline. Must be shorter than
+		/// `RevealTimeout`, after which open games expire anyway.
+		#[pallet::constant]
+		type InactivityTimeout: Get<Self::BlockNumber>;
+
+		/// Largest number of games an account can create within a single block.
+		#[pallet::constant]
+		type MaxGamesPerBlockPerAccount: Get<u32>;
+
+		/// Largest number of games created at once by `create_games_batch`.
+		#[pallet::constant]
+		type MaxBatch: Get<u32>;
+
+		/// Number of blocks after its creation past which a game can no longer be revealed.
+		/// Revealing it then returns the bets of both players instead, so that funds cannot stay
+		/// locked in a game forever whatever its timeouts.
+		#[pallet::constant]
+		type MaxGameLifetime: Get<Self::BlockNumber>;
+
+		/// Largest number of settled games whose outcome is kept, the oldest being dropped first.
+		/// None are kept when it is zero.
+		#[pallet::constant]
+		type MaxOutcomes: Get<u32>;
+
+		/// Whether a player revealing the same secret as their opponent is rejected, as a sign of
+		/// weak randomness.
+		#[pallet::constant]
+		type RejectSharedSecret: Get<bool>;
+
+		/// Share of their bet a player loses when they forfeit or let the reveal deadline pass,
+		/// the rest being returned to them.
+		#[pallet::constant]
+		type ForfeitPenalty: Get<Permill>;
+
+		/// Number of blocks that must pass between a player committing to a movement and
+		/// revealing it, so that a commitment cannot be revealed in the block it was made in.
+		#[pallet::constant]
+		type MinRevealDelay: Get<Self::BlockNumber>;
 	}
 
+	/// Version 1 added the commitment scheme version, the round history and the bet asset of each
+	/// game.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	// The pallet's runtime storage items.
 	// https://docs.substrate.io/v3/runtime/storage
+	/// Identifier that will be assigned to the next created game.
+	#[pallet::storage]
+	#[pallet::getter(fn next_game_id)]
+	pub type NextGameId<T> = StorageValue<_, GameId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn games)]
+	pub type Games<T: Config> = StorageMap<_, Blake2_128Concat, GameId, GameStateOf<T>>;
+
+	/// Number of games created and not yet settled, cancelled or expired.
+	#[pallet::storage]
+	#[pallet::getter(fn open_games)]
+	pub type OpenGames<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Wins, losses and draws of the first account of each pair against the second one, for pairs
+	/// of accounts in ascending order. Read it through `head_to_head`.
+	#[pallet::storage]
+	pub type HeadToHeadRecords<T: Config> =
+		StorageMap<_, Blake2_128Concat, (T::AccountId, T::AccountId), (u32, u32, u32), ValueQuery>;
+
+	/// Number of games each account won against an opponent.
+	#[pallet::storage]
+	#[pallet::getter(fn wins)]
+	pub type Wins<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Season games are settled in, moved on by `advance_season`.
+	#[pallet::storage]
+	#[pallet::getter(fn current_season)]
+	pub type CurrentSeason<T> = StorageValue<_, Season, ValueQuery>;
+
+	/// Number of games each account won in each season, for leaderboards starting afresh every
+	/// season.
+	#[pallet::storage]
+	#[pallet::getter(fn season_wins)]
+	pub type SeasonWins<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, Season, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Player of each settled game who asked to play it again, until their opponent agrees.
+	#[pallet::storage]
+	#[pallet::getter(fn rematch_requests)]
+	pub type RematchRequests<T: Config> =
+		StorageMap<_, Blake2_128Concat, GameId, T::AccountId, OptionQuery>;
+
+	/// Smallest bet the second player of each open game can join with when it is lower than the
+	/// bet of the first one, set by the creator of the game.
+	#[pallet::storage]
+	#[pallet::getter(fn min_accept)]
+	pub type MinAccepts<T: Config> = StorageMap<_, Blake2_128Concat, GameId, BalanceOf<T>>;
+
+	/// Outcome of the last `MaxOutcomes` settled games, kept after they are purged.
 	#[pallet::storage]
-	#[pallet::getter(fn something)]
-	// Learn more about declaring storage items:
-	// https://docs.substrate.io/v3/runtime/storage#declaring-storage-items
-	pub type Something<T> = StorageValue<_, u32>;
+	#[pallet::getter(fn outcome)]
+	pub type Outcomes<T: Config> = StorageMap<_, Blake2_128Concat, GameId, GameOutcomeOf<T>>;
+
+	/// Games whose outcome is kept in `Outcomes`, oldest first.
+	#[pallet::storage]
+	pub type OutcomeQueue<T: Config> =
+		StorageValue<_, BoundedVec<GameId, T::MaxOutcomes>, ValueQuery>;
+
+	/// Account that sponsored each game in play and the amount it added to the pot, reserved from
+	/// it until the game is settled.
+	#[pallet::storage]
+	#[pallet::getter(fn sponsorship)]
+	pub type Sponsorships<T: Config> =
+		StorageMap<_, Blake2_128Concat, GameId, (T::AccountId, BalanceOf<T>), OptionQuery>;
+
+	/// Accounts banned from creating and joining games.
+	#[pallet::storage]
+	pub type Blocked<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// Sequence number of the next `PlayerMadeMovement`, `MoveRevealed` or `GameFinished` event of
+	/// each game, carried by those events so indexers can order them within a block.
+	#[pallet::storage]
+	#[pallet::getter(fn event_seq)]
+	pub type EventSeq<T> = StorageMap<_, Blake2_128Concat, GameId, u32, ValueQuery>;
+
+	/// Game created as the rematch of each settled game.
+	#[pallet::storage]
+	#[pallet::getter(fn rematches)]
+	pub type Rematches<T> = StorageMap<_, Blake2_128Concat, GameId, GameId, OptionQuery>;
+
+	/// Lowest game id that may still expire while open. Open games expire `RevealTimeout` blocks
+	/// after their creation, so their deadlines grow with their ids and every game below it was
+	/// already joined, cancelled or expired.
+	#[pallet::storage]
+	#[pallet::getter(fn expiry_cursor)]
+	pub type ExpiryCursor<T> = StorageValue<_, GameId, ValueQuery>;
+
+	/// Number of games each account created in the current block, cleared at the start of every
+	/// block.
+	#[pallet::storage]
+	#[pallet::getter(fn games_created_in_block)]
+	pub type GamesCreatedInBlock<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Unsettled games each player takes part in, in the order they joined them.
+	#[pallet::storage]
+	#[pallet::getter(fn player_games)]
+	pub type PlayerGames<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<GameId, T::MaxGamesPerPlayer>,
+		ValueQuery,
+	>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Mode and number of rounds of the open games created at genesis, numbered from
+		/// `first_game_id`.
+		pub initial_games: Vec<(GameMode, u8)>,
+		/// Id of the first game, so that a chain can carry on the numbering of another one.
+		pub first_game_id: GameId,
+		/// Number of wins each account starts with.
+		pub wins: Vec<(T::AccountId, u32)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { initial_games: Vec::new(), first_game_id: 0, wins: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let next_game_id = self
+				.first_game_id
+				.checked_add(self.initial_games.len() as GameId)
+				.expect("Genesis game ids must not overflow");
+			for (game_id, (mode, rounds)) in (self.first_game_id..).zip(&self.initial_games) {
+				assert!(
+					*rounds % 2 == 1 && *rounds <= T::MaxRounds::get(),
+					"Genesis games must be played over an odd number of rounds up to MaxRounds"
+				);
+				<Games<T>>::insert(
+					game_id,
+					GameState {
+						mode: *mode,
+						rounds: *rounds,
+						reveal_deadline: Some(Pallet::<T>::round_deadline(None)),
+						join_deadline: Some(Pallet::<T>::join_deadline()),
+						..Default::default()
+					},
+				);
+			}
+			assert!(
+				self.initial_games.len() <= T::MaxOpenGames::get() as usize,
+				"Genesis games must not exceed MaxOpenGames"
+			);
+			<NextGameId<T>>::put(next_game_id);
+			<ExpiryCursor<T>>::put(self.first_game_id);
+			<OpenGames<T>>::put(self.initial_games.len() as u32);
+			for (account, wins) in &self.wins {
+				<Wins<T>>::insert(account, wins);
+			}
+		}
+	}
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
+	/// Events of the pallet. A game played to the end emits `GameCreated`, then `PlayerJoined` and
+	/// `PlayerMadeMovement` for each player as they join, `MoveRevealed` for each player,
+	/// `RoundFinished`, `FeeCollected` if the house took a fee, `StakeReleased` if the game has a
+	/// winner and finally `GameFinished`. Every further round emits `PlayerMadeMovement`,
+	/// `MoveRevealed` and `RoundFinished` again, in that order, before the game finishes.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Event documentation should end with an array that provides descriptive names for event
-		/// parameters. [something, who]
-		SomethingStored(u32, T::AccountId),
+		/// A new game was created and its creator paid the creation fee.
+		/// [game_id, creator, mode, rounds, fee, metadata]
+		GameCreated(GameId, T::AccountId, GameMode, u8, BalanceOf<T>, Option<GameMetadata>),
+		/// A player joined a game in the given slot, right before committing their first movement.
+		/// [game_id, player, slot]
+		PlayerJoined(GameId, T::AccountId, PlayerSlot),
+		/// A player committed a movement to a game, leaving the given number of free slots, with
+		/// the given total of bets at stake. [game_id, player, slots_remaining, pot, event_seq]
+		PlayerMadeMovement(GameId, T::AccountId, u8, BalanceOf<T>, u32),
+		/// The movement a player committed to was revealed, by themselves with `reveal_move` or
+		/// along with their opponent's with `reveal_winner`. [game_id, player, movement,
+		/// event_seq]
+		MoveRevealed(GameId, T::AccountId, GameMovement, u32),
+		/// A game was cancelled before a second player joined. [game_id]
+		GameCancelled(GameId),
+		/// An open game was removed because nobody joined it in time, or a game in play outlived
+		/// `MaxGameLifetime` and the bets of its players were returned. [game_id]
+		GameExpired(GameId),
+		/// A game whose players both let the reveal deadline pass was settled as a draw, with
+		/// both bets returned. [game_id]
+		GameAbandoned(GameId),
+		/// A round of a game was settled. [game_id, result]
+		RoundFinished(GameId, GameResult),
+		/// A game ended in a draw and its last round will be played again. [game_id]
+		RoundDrawn(GameId),
+		/// The house fee was taken from the loser's bet. [game_id, fee]
+		FeeCollected(GameId, BalanceOf<T>),
+		/// A game was settled. The result is from the point of view of the first player, the
+		/// winner is `None` on a draw and the prize is what the winner received from the loser's
+		/// bet after the house fee and from the sponsor of the game. [game_id, result, winner,
+		/// prize, event_seq]
+		GameFinished(GameId, GameResult, Option<T::AccountId>, BalanceOf<T>, u32),
+		/// Settled games were removed from storage. [count]
+		GamesPurged(u32),
+		/// The creator of a game opened or closed its reveal window. [game_id, open]
+		RevealWindowSet(GameId, bool),
+		/// Both players of a settled game agreed to play it again. [old_game_id, new_game_id]
+		RematchCreated(GameId, GameId),
+		/// A batch of games with consecutive ids starting at `start` was created.
+		/// [start, count]
+		GamesCreatedBatch(GameId, u32),
+		/// A batch of games was settled, skipping the listed games that could not be.
+		/// [settled, skipped]
+		BatchSettled(u32, u32),
+		/// An account was banned from creating and joining games. [account]
+		AccountBlocked(T::AccountId),
+		/// An account can create and join games again. [account]
+		AccountUnblocked(T::AccountId),
+		/// The creator of a game set the smallest bet a second player can join it with.
+		/// [game_id, min_accept]
+		MinAcceptSet(GameId, BalanceOf<T>),
+		/// The reserved bet of the loser of a game, minus the house fee, was moved to the winner.
+		/// [game_id, from, to, amount]
+		StakeReleased(GameId, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// The creator of a game handed its rights over the game to another account.
+		/// [game_id, from, to]
+		OwnershipTransferred(GameId, T::AccountId, T::AccountId),
+		/// A new season started. [season]
+		SeasonAdvanced(Season),
+		/// An account added to the pot of a game in play. [game_id, sponsor, amount]
+		GameSponsored(GameId, T::AccountId, BalanceOf<T>),
 	}
 
 	// Errors inform users that something went wrong.
 	#[pallet::error]
 	pub enum Error<T> {
-		/// Error names should be descriptive.
-		NoneValue,
-		/// Errors should have helpful documentation associated with them.
+		/// The game does not exist.
+		GameNotFound,
+		/// Both player slots of the game are taken.
+		GameIsFull,
+		/// The player already joined this game.
+		PlayerAlreadyInGame,
+		/// The player is not part of this game.
+		PlayerNotInGame,
+		/// Both players must have joined before movements can be revealed.
+		GameNotReady,
+		/// The revealed movement and secret do not match the commitment.
+		InvalidHash,
+		/// The player already revealed their movement.
+		MoveAlreadyRevealed,
+		/// The revealed secret is below `MinSecret`.
+		WeakSecret,
+		/// Only the player who joined a game may cancel it and only its creator may open or close
+		/// its reveal window.
+		NotGameOwner,
+		/// The number of rounds must be odd and at most `MaxRounds`.
+		InvalidRounds,
+		/// The player already committed to a movement for this round.
+		MoveAlreadyCommitted,
+		/// The player has not committed to a movement for this round.
+		MoveNotCommitted,
+		/// The player has not revealed their movement.
+		MoveNotRevealed,
+		/// The reveal deadline of the game has not passed yet.
+		RevealDeadlineNotReached,
+		/// The movement is not allowed in the mode of the game.
+		InvalidMoveForMode,
+		/// The call is not allowed in the current status of the game.
+		InvalidGameStatus,
+		/// The player cannot afford the bet.
+		InsufficientBalance,
+		/// The bet differs from the one placed by the first player.
+		StakeMismatch,
+		/// The player already takes part in `MaxGamesPerPlayer` unsettled games.
+		TooManyGames,
+		/// The second player of a game must differ from the first one.
+		CannotPlaySelf,
+		/// The game is private and the player is not the invited opponent.
+		NotInvited,
+		/// `MaxOpenGames` games are already waiting to be settled.
+		TooManyOpenGames,
+		/// A storage value would overflow.
 		StorageOverflow,
+		/// The account already created `MaxGamesPerBlockPerAccount` games in this block.
+		RateLimited,
+		/// The bet is lower than `MinBet`.
+		BetTooLow,
+		/// The bet is higher than `MaxBet`.
+		BetTooHigh,
+		/// The creator of the game closed its reveal window.
+		RevealNotOpen,
+		/// The caller already asked for a rematch of the game, or it was already played again.
+		RematchAlreadyRequested,
+		/// The revealed secret is above `MaxSecret`.
+		InvalidSecret,
+		/// The batch is empty or larger than `MaxBatch`.
+		InvalidBatchSize,
+		/// A revealed movement no longer matches its commitment.
+		CommitmentMismatch,
+		/// The account is banned from creating and joining games.
+		AccountBlocked,
+		/// The join deadline of the game has not passed yet.
+		JoinDeadlineNotReached,
+		/// The asset of the bet is not known to `Assets`.
+		UnknownAsset,
+		/// A round of the game was already won.
+		RoundAlreadyWon,
+		/// The game was already settled.
+		GameAlreadyFinished,
+		/// The game was not created by the account the caller expected.
+		CreatorMismatch,
+		/// Both players revealed the same secret while `RejectSharedSecret` is set.
+		SharedSecret,
+		/// The game already has a sponsor.
+		AlreadySponsored,
+		/// A sponsorship must add something to the pot.
+		ZeroSponsorship,
+		/// `MinRevealDelay` blocks have not passed since the movement was committed.
+		RevealTooSoon,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let creators = match <GamesCreatedInBlock<T>>::remove_all(None) {
+				sp_io::KillStorageResult::AllRemoved(removed) |
+				sp_io::KillStorageResult::SomeRemaining(removed) => removed,
+			};
+			Self::expire_open_games(now)
+				.saturating_add(T::DbWeight::get().writes(creators as Weight))
+		}
+
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::cancel_inactive_games(now, remaining_weight)
+		}
+
+		fn integrity_test() {
+			assert!(
+				T::InactivityTimeout::get() < T::RevealTimeout::get(),
+				"`InactivityTimeout` must be shorter than `RevealTimeout`, or open games expire \
+				 before they can be cancelled as inactive"
+			);
+		}
 	}
 
-	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
-	// These functions materialize as "extrinsics", which are often compared to transactions.
-	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResult {
-			// Check that the extrinsic was signed and get the signer.
-			// This function will return an error if the extrinsic is not signed.
-			// https://docs.substrate.io/v3/runtime/origins
-			let who = ensure_signed(origin)?;
-
-			// Update storage.
-			<Something<T>>::put(something);
-
-			// Emit an event.
-			Self::deposit_event(Event::SomethingStored(something, who));
-			// Return a successful DispatchResultWithPostInfo
-			Ok(())
-		}
-
-		/// An example dispatchable that may throw a custom error.
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
-		pub fn cause_error(origin: OriginFor<T>) -> DispatchResult {
-			let _who = ensure_signed(origin)?;
-
-			// Read a value from storage.
-			match <Something<T>>::get() {
-				// Return an error if the value has not been set.
-				None => return Err(Error::<T>::NoneValue.into()),
-				Some(old) => {
-					// Increment the value read from storage; will error in the event of overflow.
-					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
-					// Update the value in storage with the incremented result.
-					<Something<T>>::put(new);
+		/// Creates a new empty game played over `rounds` rounds with the movements allowed by
+		/// `mode` and emits its id. The first player to win the majority of the rounds wins the
+		/// game. With `replay_on_draw`, a game ending in a draw gets its last round replayed up to
+		/// `MaxDrawReplays` times before being settled as a draw. Setting `opponent` makes the game
+		/// private: only that account can play against the first player. `reveal_timeout` replaces
+		/// `RevealTimeout` for the game and is clamped to `[MinTimeout, MaxTimeout]`. `metadata`
+		/// labels the game, for instance with its name or a tournament tag, and is limited to
+		/// `MaxMetadataLen` bytes.
+		#[pallet::weight(T::WeightInfo::create_game())]
+		pub fn create_game(
+			origin: OriginFor<T>,
+			mode: GameMode,
+			rounds: u8,
+			replay_on_draw: bool,
+			opponent: Option<T::AccountId>,
+			reveal_timeout: Option<T::BlockNumber>,
+			metadata: Option<GameMetadata>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_create_game(
+				&who,
+				mode,
+				rounds,
+				replay_on_draw,
+				opponent,
+				reveal_timeout,
+				metadata,
+			)?;
+			Ok(())
+		}
+
+		/// Creates `count` public games with the given mode and number of rounds at once, paying
+		/// the creation fee for each. Their ids are consecutive. The games still count towards
+		/// `MaxGamesPerBlockPerAccount` and `MaxOpenGames`, and nothing is created if any of them
+		/// cannot be.
+		#[pallet::weight(T::WeightInfo::create_games_batch(*count))]
+		#[transactional]
+		pub fn create_games_batch(
+			origin: OriginFor<T>,
+			mode: GameMode,
+			rounds: u8,
+			count: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(count > 0 && count <= T::MaxBatch::get(), Error::<T>::InvalidBatchSize);
+
+			let start = <NextGameId<T>>::get();
+			start.checked_add(count.into()).ok_or(Error::<T>::StorageOverflow)?;
+			for _ in 0..count {
+				Self::do_create_game(&who, mode, rounds, false, None, None, None)?;
+			}
+
+			Self::deposit_event(Event::GamesCreatedBatch(start, count));
+			Ok(())
+		}
+
+		/// Joins a game by committing to a movement built with `SecretGameMovement::new` for this
+		/// game and the calling account, and reserves `bet` of `asset_id` from the caller. The
+		/// first player sets the bet and asset the second one has to match. The second player can
+		/// bet less, down to the `min_accept` set by the creator, in which case the bet of the
+		/// first one is lowered to match and the excess returned to them. With `expected_creator`,
+		/// the caller only joins the game if that account created it, so that they cannot join the
+		/// wrong game by racing for its id.
+		#[pallet::weight(T::WeightInfo::play_game())]
+		pub fn play_game(
+			origin: OriginFor<T>,
+			game_id: GameId,
+			secret_movement: SecretGameMovement,
+			bet: BalanceOf<T>,
+			asset_id: AssetId,
+			expected_creator: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!<Blocked<T>>::contains_key(&who), Error::<T>::AccountBlocked);
+
+			let mut game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(
+				expected_creator.map_or(true, |creator| game_state.creator == Some(creator)),
+				Error::<T>::CreatorMismatch
+			);
+			let excess = Self::check_play(game_id, &game_state, &who, bet, asset_id)?;
+			let slot = game_state
+				.add_player(PlayerMovement::new(
+					who.clone(),
+					secret_movement,
+					bet,
+					<frame_system::Pallet<T>>::block_number(),
+				))
+				.ok_or(Error::<T>::GameIsFull)?;
+			if game_state.bet.is_none() {
+				game_state.bet = Some(bet);
+				game_state.asset_id = asset_id;
+			}
+			let mut player_games = <PlayerGames<T>>::get(&who);
+			player_games.try_push(game_id).map_err(|_| Error::<T>::TooManyGames)?;
+			Self::reserve_bet(asset_id, &who, bet)?;
+			if !excess.is_zero() {
+				let first_players = [&mut game_state.player1, &mut game_state.player2]
+					.into_iter()
+					.flatten()
+					.filter(|player_movement| player_movement.player != who);
+				for player_movement in first_players {
+					T::Assets::unreserve(asset_id, &player_movement.player, excess);
+					player_movement.bet = bet;
+				}
+				game_state.bet = Some(bet);
+				game_state.pot = game_state.pot.saturating_sub(excess);
+			}
+			game_state.pot = game_state.pot.saturating_add(bet);
+			let (slots_remaining, pot) = (game_state.free_slots(), game_state.pot);
+			if slots_remaining == 0 {
+				game_state.status = GameStatus::AwaitingReveal;
+				game_state.reveal_deadline = Some(Self::round_deadline(game_state.reveal_timeout));
+				<MinAccepts<T>>::remove(game_id);
+			}
+			<Games<T>>::insert(game_id, game_state);
+			<PlayerGames<T>>::insert(&who, player_games);
+
+			Self::deposit_event(Event::PlayerJoined(game_id, who.clone(), slot));
+			let event_seq = Self::next_event_seq(game_id);
+			Self::deposit_event(Event::PlayerMadeMovement(
+				game_id,
+				who,
+				slots_remaining,
+				pot,
+				event_seq,
+			));
+			Ok(())
+		}
+
+		/// Commits the caller to a movement for the next round of a game they already play.
+		#[pallet::weight(T::WeightInfo::commit_move())]
+		pub fn commit_move(
+			origin: OriginFor<T>,
+			game_id: GameId,
+			secret_movement: SecretGameMovement,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(game_state.status == GameStatus::AwaitingMoves, Error::<T>::InvalidGameStatus);
+			let player_movement =
+				game_state.player_movement_mut(&who).ok_or(Error::<T>::PlayerNotInGame)?;
+			ensure!(!player_movement.has_committed(), Error::<T>::MoveAlreadyCommitted);
+			player_movement.secret_movement = Some(secret_movement);
+			player_movement.committed_at = <frame_system::Pallet<T>>::block_number();
+
+			let both_committed = [&game_state.player1, &game_state.player2]
+				.into_iter()
+				.flatten()
+				.all(|p| p.has_committed());
+			if both_committed {
+				game_state.status = GameStatus::AwaitingReveal;
+				game_state.reveal_deadline = Some(Self::round_deadline(game_state.reveal_timeout));
+			}
+			let (slots_remaining, pot) = (game_state.free_slots(), game_state.pot);
+			<Games<T>>::insert(game_id, game_state);
+
+			let event_seq = Self::next_event_seq(game_id);
+			Self::deposit_event(Event::PlayerMadeMovement(
+				game_id,
+				who,
+				slots_remaining,
+				pot,
+				event_seq,
+			));
+			Ok(())
+		}
+
+		/// Replaces the commitment of the sole player of a game with a new one, as long as nobody
+		/// else joined it.
+		#[pallet::weight(T::WeightInfo::recommit())]
+		pub fn recommit(
+			origin: OriginFor<T>,
+			game_id: GameId,
+			secret_movement: SecretGameMovement,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(game_state.has_free_slots(), Error::<T>::GameIsFull);
+			ensure!(game_state.status == GameStatus::Open, Error::<T>::InvalidGameStatus);
+			let player_movement =
+				game_state.player_movement_mut(&who).ok_or(Error::<T>::PlayerNotInGame)?;
+			player_movement.secret_movement = Some(secret_movement);
+			player_movement.committed_at = <frame_system::Pallet<T>>::block_number();
+			let (slots_remaining, pot) = (game_state.free_slots(), game_state.pot);
+			<Games<T>>::insert(game_id, game_state);
+
+			let event_seq = Self::next_event_seq(game_id);
+			Self::deposit_event(Event::PlayerMadeMovement(
+				game_id,
+				who,
+				slots_remaining,
+				pot,
+				event_seq,
+			));
+			Ok(())
+		}
+
+		/// Cancels a game that nobody else joined yet, returning the bet of its sole player, who is
+		/// the only one allowed to cancel it.
+		#[pallet::weight(T::WeightInfo::cancel_game())]
+		pub fn cancel_game(origin: OriginFor<T>, game_id: GameId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(game_state.has_free_slots(), Error::<T>::GameIsFull);
+			// A game claimed as a no-show keeps its sole player seated once settled.
+			ensure!(game_state.status == GameStatus::Open, Error::<T>::InvalidGameStatus);
+			let player_movement =
+				game_state.player_movement(&who).ok_or(Error::<T>::NotGameOwner)?;
+			T::Assets::unreserve(game_state.asset_id, &who, player_movement.bet);
+			<Games<T>>::remove(game_id);
+			<EventSeq<T>>::remove(game_id);
+			<MinAccepts<T>>::remove(game_id);
+			Self::remove_player_game(&who, game_id);
+			<OpenGames<T>>::mutate(|open_games| *open_games = open_games.saturating_sub(1));
+
+			Self::deposit_event(Event::GameCancelled(game_id));
+			Ok(())
+		}
+
+		/// Reveals the caller's movement along with their opponent's at once and settles the
+		/// round. The caller's own movement and secret come first, whichever slot they joined in.
+		///
+		/// Calling it on an already finished game fails with `GameAlreadyFinished`, charging only
+		/// for the game lookup. Calling it on a game that outlived `MaxGameLifetime` returns both
+		/// bets instead, as `reveal_move` does.
+		#[pallet::weight(T::WeightInfo::reveal_winner())]
+		#[transactional]
+		pub fn reveal_winner(
+			origin: OriginFor<T>,
+			game_id: GameId,
+			movement: GameMovement,
+			secret: Secret,
+			opponent_movement: GameMovement,
+			opponent_secret: Secret,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let mut game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(game_state.has_player(&who), Error::<T>::PlayerNotInGame);
+			if game_state.is_finished() {
+				return Err(Error::<T>::GameAlreadyFinished.with_weight(T::DbWeight::get().reads(1)))
+			}
+			ensure!(game_state.status == GameStatus::AwaitingReveal, Error::<T>::GameNotReady);
+			if Self::outlived(&game_state) {
+				Self::expire_game(game_id, &mut game_state);
+				<Games<T>>::insert(game_id, game_state);
+				return Ok(().into())
+			}
+			ensure!(game_state.reveal_window_open, Error::<T>::RevealNotOpen);
+
+			let (mode, scheme_version) = (game_state.mode, game_state.scheme_version);
+			let (own, opponent) = match (game_state.player1.as_mut(), game_state.player2.as_mut()) {
+				(Some(player1), Some(player2)) if player1.player == who => (player1, player2),
+				(Some(player1), Some(player2)) if player2.player == who => (player2, player1),
+				(Some(_), Some(_)) => return Err(Error::<T>::PlayerNotInGame.into()),
+				_ => return Err(Error::<T>::GameNotReady.into()),
+			};
+			ensure!(
+				mode.allows(&movement) && mode.allows(&opponent_movement),
+				Error::<T>::InvalidMoveForMode
+			);
+			Self::ensure_reveal_delay(own)?;
+			Self::ensure_reveal_delay(opponent)?;
+			Self::validate_secret(secret)?;
+			Self::validate_secret(opponent_secret)?;
+			ensure!(
+				!T::RejectSharedSecret::get() || secret != opponent_secret,
+				Error::<T>::SharedSecret
+			);
+			ensure!(
+				own.is_commitment_of(&movement, secret, game_id, scheme_version) &&
+					opponent.is_commitment_of(
+						&opponent_movement,
+						opponent_secret,
+						game_id,
+						scheme_version
+					),
+				Error::<T>::InvalidHash
+			);
+			own.reveal(movement, secret);
+			opponent.reveal(opponent_movement, opponent_secret);
+			for (player, movement) in
+				[(own.player.clone(), movement), (opponent.player.clone(), opponent_movement)]
+			{
+				let event_seq = Self::next_event_seq(game_id);
+				Self::deposit_event(Event::MoveRevealed(game_id, player, movement, event_seq));
+			}
+
+			Self::settle(game_id, &mut game_state)?;
+			<Games<T>>::insert(game_id, game_state);
+			Ok(().into())
+		}
+
+		/// Reveals the caller's own movement. With `settle`, the round is settled in the same call
+		/// when the caller is the last player to reveal. Otherwise it is left for `settle_game`,
+		/// which anyone can call. Once the game outlived `MaxGameLifetime`, nothing is revealed and
+		/// the game is settled as a draw with both bets returned.
+		#[pallet::weight(T::WeightInfo::reveal_move())]
+		#[transactional]
+		pub fn reveal_move(
+			origin: OriginFor<T>,
+			game_id: GameId,
+			movement: GameMovement,
+			secret: Secret,
+			settle: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			match game_state.status {
+				GameStatus::Open | GameStatus::AwaitingMoves =>
+					return Err(Error::<T>::GameNotReady.into()),
+				GameStatus::AwaitingReveal => (),
+				GameStatus::Settled => return Err(Error::<T>::InvalidGameStatus.into()),
+			}
+			if Self::outlived(&game_state) {
+				Self::expire_game(game_id, &mut game_state);
+				<Games<T>>::insert(game_id, game_state);
+				return Ok(())
+			}
+			ensure!(game_state.reveal_window_open, Error::<T>::RevealNotOpen);
+			let (mode, scheme_version) = (game_state.mode, game_state.scheme_version);
+			let opponent_secret = [&game_state.player1, &game_state.player2]
+				.into_iter()
+				.flatten()
+				.find(|player_movement| player_movement.player != who)
+				.and_then(|player_movement| player_movement.secret);
+			let player_movement =
+				game_state.player_movement_mut(&who).ok_or(Error::<T>::PlayerNotInGame)?;
+			ensure!(!player_movement.is_revealed(), Error::<T>::MoveAlreadyRevealed);
+			ensure!(mode.allows(&movement), Error::<T>::InvalidMoveForMode);
+			Self::ensure_reveal_delay(player_movement)?;
+			Self::validate_secret(secret)?;
+			ensure!(
+				!T::RejectSharedSecret::get() || opponent_secret != Some(secret),
+				Error::<T>::SharedSecret
+			);
+			ensure!(
+				player_movement.is_commitment_of(&movement, secret, game_id, scheme_version),
+				Error::<T>::InvalidHash
+			);
+			player_movement.reveal(movement, secret);
+			let event_seq = Self::next_event_seq(game_id);
+			Self::deposit_event(Event::MoveRevealed(game_id, who, movement, event_seq));
+
+			if settle && game_state.ready_to_settle() {
+				Self::settle(game_id, &mut game_state)?;
+			}
+			<Games<T>>::insert(game_id, game_state);
+			Ok(())
+		}
+
+		/// Settles the current round of a game whose players both revealed their movements,
+		/// paying the game out once it is decided. Any signed account can call it, so keepers can
+		/// finish games left with both movements revealed on behalf of the players.
+		#[pallet::weight(T::WeightInfo::settle_game())]
+		#[transactional]
+		pub fn settle_game(origin: OriginFor<T>, game_id: GameId) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let mut game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(game_state.status != GameStatus::Settled, Error::<T>::InvalidGameStatus);
+			ensure!(
+				game_state.status == GameStatus::AwaitingReveal && game_state.ready_to_settle(),
+				Error::<T>::GameNotReady
+			);
+
+			Self::settle(game_id, &mut game_state)?;
+			<Games<T>>::insert(game_id, game_state);
+			Ok(())
+		}
+
+		/// Settles every listed game `settle_game` would settle, skipping the others instead of
+		/// failing, so keepers can finish many games at once.
+		#[pallet::weight(T::WeightInfo::settle_games_batch(game_ids.len() as u32))]
+		pub fn settle_games_batch(
+			origin: OriginFor<T>,
+			game_ids: BoundedVec<GameId, T::MaxBatch>,
+		) -> DispatchResult {
+			ensure_signed(origin.clone())?;
+			ensure!(!game_ids.is_empty(), Error::<T>::InvalidBatchSize);
+
+			let mut settled = 0;
+			for game_id in game_ids.iter() {
+				// `settle_game` is transactional, so a skipped game leaves no changes behind.
+				if Self::settle_game(origin.clone(), *game_id).is_ok() {
+					settled += 1;
+				}
+			}
+
+			let skipped = game_ids.len() as u32 - settled;
+			Self::deposit_event(Event::BatchSettled(settled, skipped));
+			Ok(())
+		}
+
+		/// Declares the caller the winner of a game whose round deadline passed, given they
+		/// committed to or revealed their movement for the round and their opponent did not.
+		#[pallet::weight(T::WeightInfo::claim_timeout())]
+		#[transactional]
+		pub fn claim_timeout(origin: OriginFor<T>, game_id: GameId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			let player_movement =
+				game_state.player_movement(&who).ok_or(Error::<T>::PlayerNotInGame)?;
+			match game_state.status {
+				GameStatus::AwaitingMoves =>
+					ensure!(player_movement.has_committed(), Error::<T>::MoveNotCommitted),
+				GameStatus::AwaitingReveal => {
+					ensure!(player_movement.is_revealed(), Error::<T>::MoveNotRevealed);
+					// The opponent revealed too, so the round has to be settled instead.
+					ensure!(!game_state.ready_to_settle(), Error::<T>::InvalidGameStatus);
+					// The opponent cannot reveal while the window is closed.
+					ensure!(game_state.reveal_window_open, Error::<T>::RevealNotOpen);
+				},
+				GameStatus::Open | GameStatus::Settled =>
+					return Err(Error::<T>::InvalidGameStatus.into()),
+			}
+			let deadline_passed = game_state
+				.reveal_deadline
+				.map_or(false, |deadline| <frame_system::Pallet<T>>::block_number() > deadline);
+			ensure!(deadline_passed, Error::<T>::RevealDeadlineNotReached);
+
+			// Both players acting would have moved the round on, so the opponent has not.
+			let is_player1 = game_state.player1.as_ref().map_or(false, |p| p.player == who);
+			let game_result = if is_player1 { GameResult::Win } else { GameResult::Lose };
+			Self::finish_game(game_id, &mut game_state, game_result, T::ForfeitPenalty::get())?;
+			<Games<T>>::insert(game_id, game_state);
+			Ok(())
+		}
+
+		/// Abandons a game whose deadline passed with neither player having revealed their
+		/// committed movement, or with neither player having committed at all, returning both
+		/// bets and settling it as a draw without a winner. Once a round was won, the leading
+		/// player can still commit and claim the timeout, so the game cannot be abandoned and the
+		/// trailing player cannot stall to get their bet back.
+		#[pallet::weight(T::WeightInfo::resolve_stalemate())]
+		pub fn resolve_stalemate(origin: OriginFor<T>, game_id: GameId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(game_state.has_player(&who), Error::<T>::PlayerNotInGame);
+			ensure!(
+				game_state.player1_score == 0 && game_state.player2_score == 0,
+				Error::<T>::RoundAlreadyWon
+			);
+			let players = [&game_state.player1, &game_state.player2];
+			match game_state.status {
+				GameStatus::AwaitingReveal => ensure!(
+					players.into_iter().flatten().all(|p| !p.is_revealed()),
+					Error::<T>::MoveAlreadyRevealed
+				),
+				GameStatus::AwaitingMoves => ensure!(
+					players.into_iter().flatten().all(|p| !p.has_committed()),
+					Error::<T>::MoveAlreadyCommitted
+				),
+				GameStatus::Open | GameStatus::Settled =>
+					return Err(Error::<T>::InvalidGameStatus.into()),
+			}
+			let deadline_passed = game_state
+				.reveal_deadline
+				.map_or(false, |deadline| <frame_system::Pallet<T>>::block_number() > deadline);
+			ensure!(deadline_passed, Error::<T>::RevealDeadlineNotReached);
+
+			Self::refund_players(game_id, &mut game_state);
+			<Games<T>>::insert(game_id, game_state);
+
+			Self::deposit_event(Event::GameAbandoned(game_id));
+			Ok(())
+		}
+
+		/// Declares the caller the winner of a game they joined and committed to when nobody took
+		/// the other slot before its join deadline, returning their bet. The claim has to be made
+		/// before the open game expires. Nobody was beaten, so the claim does not count towards
+		/// `Wins`.
+		#[pallet::weight(T::WeightInfo::claim_no_show())]
+		pub fn claim_no_show(origin: OriginFor<T>, game_id: GameId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(game_state.status == GameStatus::Open, Error::<T>::InvalidGameStatus);
+			let player_movement = match (&game_state.player1, &game_state.player2) {
+				(Some(player1), None) if player1.player == who => player1,
+				_ => return Err(Error::<T>::PlayerNotInGame.into()),
+			};
+			ensure!(player_movement.has_committed(), Error::<T>::MoveNotCommitted);
+			let deadline_passed = game_state
+				.join_deadline
+				.map_or(false, |deadline| <frame_system::Pallet<T>>::block_number() > deadline);
+			ensure!(deadline_passed, Error::<T>::JoinDeadlineNotReached);
+
+			T::Assets::unreserve(game_state.asset_id, &who, player_movement.bet);
+			Self::remove_player_game(&who, game_id);
+			<MinAccepts<T>>::remove(game_id);
+			<OpenGames<T>>::mutate(|open_games| *open_games = open_games.saturating_sub(1));
+			game_state.pot = Zero::zero();
+			game_state.status = GameStatus::Settled;
+			game_state.game_result = GameResult::Win;
+			game_state.winner = Some(who.clone());
+			Self::record_outcome(game_id, &game_state, Zero::zero());
+			<Games<T>>::insert(game_id, game_state);
+
+			let event_seq = Self::next_event_seq(game_id);
+			Self::deposit_event(Event::GameFinished(
+				game_id,
+				GameResult::Win,
+				Some(who),
+				Zero::zero(),
+				event_seq,
+			));
+			Ok(())
+		}
+
+		/// Gives up a game both players joined, paying the caller's bet out to their opponent as
+		/// if they lost it.
+		#[pallet::weight(T::WeightInfo::forfeit())]
+		#[transactional]
+		pub fn forfeit(origin: OriginFor<T>, game_id: GameId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(game_state.has_player(&who), Error::<T>::PlayerNotInGame);
+			match game_state.status {
+				GameStatus::Open => return Err(Error::<T>::GameNotReady.into()),
+				GameStatus::AwaitingMoves | GameStatus::AwaitingReveal => (),
+				GameStatus::Settled => return Err(Error::<T>::InvalidGameStatus.into()),
+			}
+
+			let is_player1 = game_state.player1.as_ref().map_or(false, |p| p.player == who);
+			let game_result = if is_player1 { GameResult::Lose } else { GameResult::Win };
+			Self::finish_game(game_id, &mut game_state, game_result, T::ForfeitPenalty::get())?;
+			<Games<T>>::insert(game_id, game_state);
+			Ok(())
+		}
+
+		/// Opens or closes the reveal window of a game, which only its creator can do. Opening a
+		/// closed window once the game is in play gives the players a full `RevealTimeout` to
+		/// reveal. The window cannot be changed once a player revealed their movement for the
+		/// round, or the creator could keep a revealed player from claiming the timeout.
+		#[pallet::weight(T::WeightInfo::set_reveal_window())]
+		pub fn set_reveal_window(
+			origin: OriginFor<T>,
+			game_id: GameId,
+			open: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(game_state.creator.as_ref() == Some(&who), Error::<T>::NotGameOwner);
+			ensure!(game_state.status != GameStatus::Settled, Error::<T>::InvalidGameStatus);
+			let revealed = [&game_state.player1, &game_state.player2]
+				.into_iter()
+				.flatten()
+				.any(|player_movement| player_movement.is_revealed());
+			ensure!(!revealed, Error::<T>::MoveAlreadyRevealed);
+
+			if open && !game_state.reveal_window_open && game_state.status != GameStatus::Open {
+				game_state.reveal_deadline = Some(Self::round_deadline(game_state.reveal_timeout));
+			}
+			game_state.reveal_window_open = open;
+			<Games<T>>::insert(game_id, game_state);
+
+			Self::deposit_event(Event::RevealWindowSet(game_id, open));
+			Ok(())
+		}
+
+		/// Bans an account from creating and joining games. Games it already plays are unaffected.
+		#[pallet::weight(T::WeightInfo::block_account())]
+		pub fn block_account(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+			<Blocked<T>>::insert(&account, ());
+			Self::deposit_event(Event::AccountBlocked(account));
+			Ok(())
+		}
+
+		/// Lifts the ban of an account.
+		#[pallet::weight(T::WeightInfo::unblock_account())]
+		pub fn unblock_account(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+			<Blocked<T>>::remove(&account);
+			Self::deposit_event(Event::AccountUnblocked(account));
+			Ok(())
+		}
+
+		/// Removes up to `limit` settled games from storage. At most `MaxOpenGames` unsettled
+		/// games are skipped over while looking for them.
+		#[pallet::weight(
+			T::WeightInfo::purge_settled(*limit)
+				.saturating_add(T::DbWeight::get().reads(T::MaxOpenGames::get().into()))
+		)]
+		pub fn purge_settled(origin: OriginFor<T>, limit: u32) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let settled: Vec<GameId> = <Games<T>>::iter()
+				.filter(|(_, game_state)| game_state.status == GameStatus::Settled)
+				.map(|(game_id, _)| game_id)
+				.take(limit as usize)
+				.collect();
+			for game_id in &settled {
+				<Games<T>>::remove(game_id);
+				<RematchRequests<T>>::remove(game_id);
+				<Rematches<T>>::remove(game_id);
+				<EventSeq<T>>::remove(game_id);
+			}
+
+			Self::deposit_event(Event::GamesPurged(settled.len() as u32));
+			Ok(())
+		}
+
+		/// Asks to play a settled game again. Once both of its players asked, a new game with the
+		/// same parameters is created by the second one, who pays the creation fee. Both players
+		/// are seated in it with the same bet reserved again and have to commit to their first
+		/// movement with `commit_move`.
+		#[pallet::weight(T::WeightInfo::request_rematch())]
+		#[transactional]
+		pub fn request_rematch(origin: OriginFor<T>, game_id: GameId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(game_state.has_player(&who), Error::<T>::PlayerNotInGame);
+			ensure!(game_state.status == GameStatus::Settled, Error::<T>::InvalidGameStatus);
+			ensure!(!<Rematches<T>>::contains_key(game_id), Error::<T>::RematchAlreadyRequested);
+
+			match <RematchRequests<T>>::get(game_id) {
+				Some(requester) if requester == who =>
+					Err(Error::<T>::RematchAlreadyRequested.into()),
+				Some(_) => {
+					let new_game_id = Self::create_rematch(&who, &game_state)?;
+					<RematchRequests<T>>::remove(game_id);
+					<Rematches<T>>::insert(game_id, new_game_id);
+					Self::deposit_event(Event::RematchCreated(game_id, new_game_id));
+					Ok(())
+				},
+				None => {
+					<RematchRequests<T>>::insert(game_id, who);
+					Ok(())
+				},
+			}
+		}
+
+		/// Lets the second player join an open game with a bet as low as `min_accept` when the
+		/// first player bet more, which only the creator of the game can do.
+		#[pallet::weight(T::WeightInfo::set_min_accept())]
+		pub fn set_min_accept(
+			origin: OriginFor<T>,
+			game_id: GameId,
+			min_accept: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(game_state.creator.as_ref() == Some(&who), Error::<T>::NotGameOwner);
+			ensure!(game_state.status == GameStatus::Open, Error::<T>::InvalidGameStatus);
+			<MinAccepts<T>>::insert(game_id, min_accept);
+
+			Self::deposit_event(Event::MinAcceptSet(game_id, min_accept));
+			Ok(())
+		}
+
+		/// Makes `new_owner` the creator of a game nobody else joined yet, handing them the right
+		/// to set its reveal window and smallest accepted bet and, in a private game, the seat
+		/// of the creator. The creation fee was burnt, so there is no deposit to move along.
+		#[pallet::weight(T::WeightInfo::transfer_ownership())]
+		pub fn transfer_ownership(
+			origin: OriginFor<T>,
+			game_id: GameId,
+			new_owner: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!<Blocked<T>>::contains_key(&new_owner), Error::<T>::AccountBlocked);
+
+			let mut game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(game_state.creator.as_ref() == Some(&who), Error::<T>::NotGameOwner);
+			ensure!(game_state.has_free_slots(), Error::<T>::GameIsFull);
+			ensure!(game_state.status == GameStatus::Open, Error::<T>::InvalidGameStatus);
+			game_state.creator = Some(new_owner.clone());
+			<Games<T>>::insert(game_id, game_state);
+
+			Self::deposit_event(Event::OwnershipTransferred(game_id, who, new_owner));
+			Ok(())
+		}
+
+		/// Starts a new season, whose games are ranked apart from those of earlier seasons. The
+		/// wins of earlier seasons stay in `SeasonWins`.
+		#[pallet::weight(T::WeightInfo::advance_season())]
+		pub fn advance_season(origin: OriginFor<T>) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let season =
+				<CurrentSeason<T>>::get().checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+			<CurrentSeason<T>>::put(season);
+
+			Self::deposit_event(Event::SeasonAdvanced(season));
+			Ok(())
+		}
+
+		/// Adds `amount` of the asset of a game in play to its pot, reserved from the caller until
+		/// the game is settled. The winner receives it free of the house fee, and the caller gets
+		/// it back if the game ends without a winner. A game has at most one sponsor.
+		#[pallet::weight(T::WeightInfo::sponsor_game())]
+		pub fn sponsor_game(
+			origin: OriginFor<T>,
+			game_id: GameId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroSponsorship);
+
+			let mut game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			match game_state.status {
+				GameStatus::AwaitingMoves | GameStatus::AwaitingReveal => (),
+				GameStatus::Open | GameStatus::Settled =>
+					return Err(Error::<T>::InvalidGameStatus.into()),
+			}
+			ensure!(!<Sponsorships<T>>::contains_key(game_id), Error::<T>::AlreadySponsored);
+			Self::reserve_bet(game_state.asset_id, &who, amount)?;
+			game_state.pot = game_state.pot.saturating_add(amount);
+			<Games<T>>::insert(game_id, game_state);
+			<Sponsorships<T>>::insert(game_id, (who.clone(), amount));
+
+			Self::deposit_event(Event::GameSponsored(game_id, who, amount));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Creates a new empty game on behalf of `creator`, burning the creation fee from them, and
+		/// returns its id, so other pallets can create games without going through the extrinsic.
+		pub fn do_create_game(
+			creator: &T::AccountId,
+			mode: GameMode,
+			rounds: u8,
+			replay_on_draw: bool,
+			opponent: Option<T::AccountId>,
+			reveal_timeout: Option<T::BlockNumber>,
+			metadata: Option<GameMetadata>,
+		) -> Result<GameId, DispatchError> {
+			ensure!(!<Blocked<T>>::contains_key(creator), Error::<T>::AccountBlocked);
+			ensure!(rounds % 2 == 1 && rounds <= T::MaxRounds::get(), Error::<T>::InvalidRounds);
+			let reveal_timeout = reveal_timeout
+				.map(|timeout| timeout.clamp(T::MinTimeout::get(), T::MaxTimeout::get()));
+
+			let game_id = <NextGameId<T>>::get();
+			let next_game_id = game_id.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+			let games_created = <GamesCreatedInBlock<T>>::get(creator);
+			ensure!(games_created < T::MaxGamesPerBlockPerAccount::get(), Error::<T>::RateLimited);
+			let open_games = <OpenGames<T>>::get();
+			ensure!(open_games < T::MaxOpenGames::get(), Error::<T>::TooManyOpenGames);
+			let fee = T::GameCreationFee::get();
+			// Dropping the imbalance burns the fee.
+			T::Currency::withdraw(
+				creator,
+				fee,
+				WithdrawReasons::FEE,
+				ExistenceRequirement::KeepAlive,
+			)
+			.map_err(|_| Error::<T>::InsufficientBalance)?;
+			<Games<T>>::insert(
+				game_id,
+				GameState {
+					mode,
+					rounds,
+					replay_on_draw,
+					creator: Some(creator.clone()),
+					opponent,
+					reveal_timeout,
+					// Open games expire after `RevealTimeout` whatever their own timeout, so that
+					// their deadlines grow with their ids as the expiry sweep expects.
+					reveal_deadline: Some(Self::round_deadline(None)),
+					join_deadline: Some(Self::join_deadline()),
+					created_at: <frame_system::Pallet<T>>::block_number(),
+					metadata: metadata.clone(),
+					..Default::default()
+				},
+			);
+			<NextGameId<T>>::put(next_game_id);
+			<OpenGames<T>>::put(open_games + 1);
+			<GamesCreatedInBlock<T>>::insert(creator, games_created + 1);
+
+			Self::deposit_event(Event::GameCreated(
+				game_id,
+				creator.clone(),
+				mode,
+				rounds,
+				fee,
+				metadata,
+			));
+			Ok(game_id)
+		}
+
+		/// Creates a game with the parameters of `game_state` on behalf of `creator`, with its two
+		/// players seated, their bets reserved and waiting for their first movements.
+		fn create_rematch(
+			creator: &T::AccountId,
+			game_state: &GameStateOf<T>,
+		) -> Result<GameId, DispatchError> {
+			let game_id = Self::do_create_game(
+				creator,
+				game_state.mode,
+				game_state.rounds,
+				game_state.replay_on_draw,
+				game_state.opponent.clone(),
+				game_state.reveal_timeout,
+				game_state.metadata.clone(),
+			)?;
+			let bet = game_state.bet.unwrap_or_else(Zero::zero);
+			let mut rematch = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			for player in [&game_state.player1, &game_state.player2].into_iter().flatten() {
+				ensure!(!<Blocked<T>>::contains_key(&player.player), Error::<T>::AccountBlocked);
+				<PlayerGames<T>>::try_mutate(&player.player, |player_games| {
+					player_games.try_push(game_id).map_err(|_| Error::<T>::TooManyGames)
+				})?;
+				Self::reserve_bet(game_state.asset_id, &player.player, bet)?;
+				rematch
+					.add_player(PlayerMovement {
+						player: player.player.clone(),
+						secret_movement: None,
+						movement: None,
+						secret: None,
+						bet,
+						committed_at: <frame_system::Pallet<T>>::block_number(),
+					})
+					.ok_or(Error::<T>::GameIsFull)?;
+			}
+			rematch.bet = Some(bet);
+			rematch.asset_id = game_state.asset_id;
+			rematch.pot = bet.saturating_add(bet);
+			rematch.status = GameStatus::AwaitingMoves;
+			rematch.reveal_deadline = Some(Self::round_deadline(rematch.reveal_timeout));
+			<Games<T>>::insert(game_id, rematch);
+			Ok(game_id)
+		}
+
+		/// Runs the checks of `play_game` for `account` joining a game with a bet of `bet` in
+		/// `asset_id` without touching storage, so that clients can tell whether the call would
+		/// go through before submitting it.
+		pub fn dry_run_play(
+			game_id: GameId,
+			account: &T::AccountId,
+			bet: BalanceOf<T>,
+			asset_id: AssetId,
+		) -> DispatchResult {
+			ensure!(!<Blocked<T>>::contains_key(account), Error::<T>::AccountBlocked);
+			let game_state = <Games<T>>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			Self::check_play(game_id, &game_state, account, bet, asset_id)?;
+			let player_games = <PlayerGames<T>>::decode_len(account).unwrap_or_default();
+			ensure!((player_games as u32) < T::MaxGamesPerPlayer::get(), Error::<T>::TooManyGames);
+			T::Assets::ensure_can_reserve(asset_id, account, bet)
+				.map_err(|error| Self::bet_error(error).into())
+		}
+
+		/// Ids of the unsettled games `account` takes part in, in ascending order.
+		pub fn games_for_account(account: &T::AccountId) -> Vec<GameId> {
+			let mut game_ids = <PlayerGames<T>>::get(account).into_inner();
+			game_ids.sort_unstable();
+			game_ids
+		}
+
+		/// Result and winner of a game, `GameResult::NotPlayed` until it is settled and `None` if
+		/// it does not exist.
+		pub fn result_of(game_id: GameId) -> Option<(GameResult, Option<T::AccountId>)> {
+			<Games<T>>::get(game_id).map(|game_state| (game_state.game_result, game_state.winner))
+		}
+
+		/// Result and winner of a round where the first player plays `player1` and the second
+		/// `player2`, without touching storage.
+		pub fn preview_result(
+			player1: GameMovement,
+			player2: GameMovement,
+		) -> (GameResult, Winner) {
+			let game_result = player1.play(&player2);
+			let winner = game_result.winner();
+			(game_result, winner)
+		}
+
+		/// Results of the rounds of a game settled so far, `None` if it does not exist.
+		pub fn round_history(game_id: GameId) -> Option<Vec<GameResult>> {
+			<Games<T>>::get(game_id).map(|game_state| game_state.round_history().to_vec())
+		}
+
+		/// Unsettled games of `account`, in the order they joined them, sorted by what they have
+		/// to do about them, along with their settled games still in `Outcomes`.
+		pub fn player_dashboard(account: &T::AccountId) -> PlayerDashboard {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let passed = |deadline: Option<T::BlockNumber>| deadline.map_or(false, |d| now > d);
+			let mut dashboard = PlayerDashboard::default();
+			for game_id in <PlayerGames<T>>::get(account).into_inner() {
+				let game_state = match <Games<T>>::get(game_id) {
+					Some(game_state) => game_state,
+					None => continue,
+				};
+				let acted = match (game_state.status, game_state.player_movement(account)) {
+					(GameStatus::AwaitingReveal, Some(player_movement)) =>
+						player_movement.is_revealed(),
+					(_, Some(player_movement)) => player_movement.has_committed(),
+					(_, None) => continue,
+				};
+				let bucket = match game_state.status {
+					GameStatus::Settled => continue,
+					GameStatus::Open if passed(game_state.join_deadline) =>
+						&mut dashboard.claimable,
+					GameStatus::Open => &mut dashboard.waiting_opponent,
+					GameStatus::AwaitingReveal if game_state.ready_to_settle() =>
+						&mut dashboard.claimable,
+					_ if !acted => &mut dashboard.to_reveal,
+					GameStatus::AwaitingReveal if !game_state.reveal_window_open =>
+						&mut dashboard.waiting_opponent,
+					_ if passed(game_state.reveal_deadline) => &mut dashboard.claimable,
+					_ => &mut dashboard.waiting_opponent,
+				};
+				bucket.push(game_id);
+			}
+			let played = |player: &Option<(T::AccountId, Option<GameMovement>, BalanceOf<T>)>| {
+				player.as_ref().map_or(false, |(player, ..)| player == account)
+			};
+			dashboard.finished = <OutcomeQueue<T>>::get()
+				.into_inner()
+				.into_iter()
+				.filter(|game_id| {
+					<Outcomes<T>>::get(game_id).map_or(false, |outcome| {
+						played(&outcome.player1) || played(&outcome.player2)
+					})
+				})
+				.collect();
+			dashboard
+		}
+
+		/// State of a game along with the figures derived from it, `None` if it does not exist.
+		pub fn game_details(game_id: GameId) -> Option<GameDetailsOf<T>> {
+			let game_state = <Games<T>>::get(game_id)?;
+			let blocks_until_timeout = match (game_state.status, game_state.reveal_deadline) {
+				(GameStatus::Settled, _) | (_, None) => None,
+				(_, Some(deadline)) =>
+					Some(deadline.saturating_sub(<frame_system::Pallet<T>>::block_number())),
+			};
+			Some(GameDetails {
+				status: game_state.status,
+				pot: game_state.pot,
+				blocks_until_timeout,
+				game_state,
+			})
+		}
+
+		/// Largest number of bytes a game takes in storage: its encoded state along with its key,
+		/// made of the storage prefix, the hash of its id and the id itself, and its entry in the
+		/// unsettled games of each of its players.
+		pub fn per_game_storage_bytes() -> u32 {
+			// Twox128 hashes of the pallet and storage names, then the Blake2_128 hash of the id.
+			let key = 16 + 16 + 16 + GameId::max_encoded_len();
+			let player_games = 2 * GameId::max_encoded_len();
+			(key + GameStateOf::<T>::max_encoded_len() + player_games) as u32
+		}
+
+		/// Number of unsettled games, counted by iterating over every game, to check `OpenGames`
+		/// against in migrations and tests.
+		pub fn count_open_games() -> u32 {
+			<Games<T>>::iter_values().filter(|game_state| !game_state.is_finished()).count() as u32
+		}
+
+		/// Sum of the bets and sponsorships at stake in every game, added up by iterating over
+		/// them, to check the balances reserved by the players and sponsors against.
+		pub fn total_at_stake() -> BalanceOf<T> {
+			<Games<T>>::iter_values()
+				.fold(Zero::zero(), |total, game_state| total.saturating_add(game_state.pot))
+		}
+
+		/// Removes up to `MaxExpirySweep` open games whose deadline passed before `now`, returning
+		/// the bet of their sole player if any, and moves the expiry cursor past every game that
+		/// can no longer expire.
+		fn expire_open_games(now: T::BlockNumber) -> Weight {
+			let next_game_id = <NextGameId<T>>::get();
+			let mut cursor = <ExpiryCursor<T>>::get();
+			let (mut reads, mut writes) = (2, 0);
+			for _ in 0..T::MaxExpirySweep::get() {
+				if cursor >= next_game_id {
+					break
+				}
+				reads += 1;
+				if let Some(game_state) = <Games<T>>::get(cursor) {
+					let expired = match game_state.reveal_deadline {
+						Some(deadline) if game_state.status == GameStatus::Open => {
+							// Later games were created later, so none of them expired either.
+							if deadline >= now {
+								break
+							}
+							true
+						},
+						_ => false,
+					};
+					if expired {
+						if game_state.player1.is_some() {
+							reads += 2;
+							writes += 2;
+						}
+						Self::remove_open_game(cursor, &game_state);
+						reads += 1;
+						writes += 2;
+						Self::deposit_event(Event::GameExpired(cursor));
+					}
+				}
+				cursor += 1;
+			}
+			<ExpiryCursor<T>>::put(cursor);
+			writes += 1;
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		/// Cancels the open games created more than `InactivityTimeout` blocks before `now`,
+		/// returning the bet of their sole player if any, for as long as `remaining_weight` allows.
+		/// Like the expiry sweep, it moves the expiry cursor past every game that can no longer
+		/// expire.
+		fn cancel_inactive_games(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let per_game = T::WeightInfo::cancel_inactive_game();
+			let mut consumed = T::DbWeight::get().reads_writes(2, 1);
+			if consumed.saturating_add(per_game) > remaining_weight {
+				return 0
+			}
+
+			let next_game_id = <NextGameId<T>>::get();
+			let mut cursor = <ExpiryCursor<T>>::get();
+			while cursor < next_game_id && consumed.saturating_add(per_game) <= remaining_weight {
+				if let Some(game_state) = <Games<T>>::get(cursor) {
+					if game_state.status == GameStatus::Open {
+						// Later games were created later, so none of them is inactive either.
+						if game_state.created_at.saturating_add(T::InactivityTimeout::get()) >= now
+						{
+							break
+						}
+						Self::remove_open_game(cursor, &game_state);
+						Self::deposit_event(Event::GameCancelled(cursor));
+					}
+				}
+				consumed = consumed.saturating_add(per_game);
+				cursor += 1;
+			}
+			<ExpiryCursor<T>>::put(cursor);
+			consumed
+		}
+
+		/// Fails unless `MinRevealDelay` blocks passed since `player_movement` was committed.
+		fn ensure_reveal_delay(player_movement: &PlayerMovementOf<T>) -> DispatchResult {
+			let earliest = player_movement.committed_at.saturating_add(T::MinRevealDelay::get());
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= earliest,
+				Error::<T>::RevealTooSoon
+			);
+			Ok(())
+		}
+
+		/// Whether a game was created more than `MaxGameLifetime` blocks ago.
+		fn outlived(game_state: &GameStateOf<T>) -> bool {
+			<frame_system::Pallet<T>>::block_number() >
+				game_state.created_at.saturating_add(T::MaxGameLifetime::get())
+		}
+
+		/// Settles a game that outlived `MaxGameLifetime` like a stalemate, returning the bets of
+		/// both players.
+		fn expire_game(game_id: GameId, game_state: &mut GameStateOf<T>) {
+			Self::refund_players(game_id, game_state);
+			Self::deposit_event(Event::GameExpired(game_id));
+		}
+
+		/// Returns the bets of the players of a game in play and settles it as a draw without a
+		/// winner.
+		fn refund_players(game_id: GameId, game_state: &mut GameStateOf<T>) {
+			for player_movement in [&game_state.player1, &game_state.player2].into_iter().flatten()
+			{
+				T::Assets::unreserve(
+					game_state.asset_id,
+					&player_movement.player,
+					player_movement.bet,
+				);
+				Self::remove_player_game(&player_movement.player, game_id);
+			}
+			Self::return_sponsorship(game_id, game_state.asset_id);
+			<OpenGames<T>>::mutate(|open_games| *open_games = open_games.saturating_sub(1));
+			game_state.pot = Zero::zero();
+			game_state.status = GameStatus::Settled;
+			game_state.game_result = GameResult::Draw;
+			game_state.winner = None;
+			Self::record_outcome(game_id, game_state, Zero::zero());
+		}
+
+		/// Moves the sponsorship of a game to `winner`, returning the amount they received.
+		fn pay_sponsorship(
+			game_id: GameId,
+			asset_id: AssetId,
+			winner: &T::AccountId,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			match <Sponsorships<T>>::take(game_id) {
+				Some((sponsor, amount)) => {
+					let missing =
+						T::Assets::repatriate_reserved(asset_id, &sponsor, winner, amount)?;
+					Ok(amount.saturating_sub(missing))
+				},
+				None => Ok(Zero::zero()),
+			}
+		}
+
+		/// Returns the sponsorship of a game settled without a winner to its sponsor.
+		fn return_sponsorship(game_id: GameId, asset_id: AssetId) {
+			if let Some((sponsor, amount)) = <Sponsorships<T>>::take(game_id) {
+				T::Assets::unreserve(asset_id, &sponsor, amount);
+			}
+		}
+
+		/// Keeps the outcome of a game that was just settled with `fee` taken by the house,
+		/// dropping the oldest outcome kept once there are `MaxOutcomes` of them.
+		fn record_outcome(game_id: GameId, game_state: &GameStateOf<T>, fee: BalanceOf<T>) {
+			let max_outcomes = T::MaxOutcomes::get();
+			if max_outcomes == 0 {
+				return
+			}
+			<OutcomeQueue<T>>::mutate(|queue| {
+				if queue.len() as u32 >= max_outcomes {
+					<Outcomes<T>>::remove(queue.remove(0));
+				}
+				// There is room for it now.
+				let _ = queue.try_push(game_id);
+			});
+			let player = |slot: &Option<PlayerMovementOf<T>>| {
+				slot.as_ref().map(|p| (p.player.clone(), p.movement, p.bet))
+			};
+			<Outcomes<T>>::insert(
+				game_id,
+				GameOutcome {
+					player1: player(&game_state.player1),
+					player2: player(&game_state.player2),
+					game_result: game_state.game_result.clone(),
+					winner: game_state.winner.clone(),
+					fee,
+					settled_at: <frame_system::Pallet<T>>::block_number(),
+					season: <CurrentSeason<T>>::get(),
+				},
+			);
+		}
+
+		/// Removes an open game, returning the bet of its sole player if any.
+		fn remove_open_game(game_id: GameId, game_state: &GameStateOf<T>) {
+			if let Some(player_movement) = &game_state.player1 {
+				T::Assets::unreserve(
+					game_state.asset_id,
+					&player_movement.player,
+					player_movement.bet,
+				);
+				Self::remove_player_game(&player_movement.player, game_id);
+			}
+			<Games<T>>::remove(game_id);
+			<EventSeq<T>>::remove(game_id);
+			<MinAccepts<T>>::remove(game_id);
+			<OpenGames<T>>::mutate(|open_games| *open_games = open_games.saturating_sub(1));
+		}
+
+		/// Wins, losses and draws of `who` in the games settled against `opponent`.
+		pub fn head_to_head(who: &T::AccountId, opponent: &T::AccountId) -> (u32, u32, u32) {
+			if who <= opponent {
+				<HeadToHeadRecords<T>>::get((who, opponent))
+			} else {
+				let (wins, losses, draws) = <HeadToHeadRecords<T>>::get((opponent, who));
+				(losses, wins, draws)
+			}
+		}
+
+		/// Adds the outcome of a game between `player1` and `player2` to their head-to-head record.
+		fn record_head_to_head(
+			player1: &T::AccountId,
+			player2: &T::AccountId,
+			game_result: &GameResult,
+		) {
+			let (key, game_result) = if player1 <= player2 {
+				((player1, player2), game_result.clone())
+			} else {
+				((player2, player1), game_result.inverse())
+			};
+			<HeadToHeadRecords<T>>::mutate(key, |(wins, losses, draws)| match game_result {
+				GameResult::Win => *wins = wins.saturating_add(1),
+				GameResult::Lose => *losses = losses.saturating_add(1),
+				GameResult::Draw => *draws = draws.saturating_add(1),
+				GameResult::NotPlayed => (),
+			});
+		}
+
+		/// Drops `game_id` from the unsettled games of `who`.
+		fn remove_player_game(who: &T::AccountId, game_id: GameId) {
+			<PlayerGames<T>>::mutate_exists(who, |game_ids| {
+				if let Some(ids) = game_ids.as_mut() {
+					ids.retain(|id| *id != game_id);
+				}
+				if game_ids.as_ref().map_or(false, |ids| ids.is_empty()) {
+					*game_ids = None;
+				}
+			});
+		}
+
+		/// Sequence number of the next sequenced event of `game_id`, which it moves on.
+		fn next_event_seq(game_id: GameId) -> u32 {
+			<EventSeq<T>>::mutate(game_id, |event_seq| {
+				let current = *event_seq;
+				*event_seq = event_seq.saturating_add(1);
+				current
+			})
+		}
+
+		/// Checks that a revealed secret lies in `[MinSecret, MaxSecret]`.
+		fn validate_secret(secret: Secret) -> DispatchResult {
+			ensure!(secret >= T::MinSecret::get(), Error::<T>::WeakSecret);
+			ensure!(secret <= T::MaxSecret::get(), Error::<T>::InvalidSecret);
+			Ok(())
+		}
+
+		/// Reserves the bet of `who` in `asset_id`, telling an unknown asset apart from a balance
+		/// that cannot cover it.
+		fn reserve_bet(asset_id: AssetId, who: &T::AccountId, bet: BalanceOf<T>) -> DispatchResult {
+			T::Assets::reserve(asset_id, who, bet).map_err(|error| Self::bet_error(error).into())
+		}
+
+		/// Error reported to a player whose bet could not be reserved.
+		fn bet_error(error: DispatchError) -> Error<T> {
+			match error {
+				DispatchError::Token(TokenError::UnknownAsset) => Error::<T>::UnknownAsset,
+				_ => Error::<T>::InsufficientBalance,
+			}
+		}
+
+		/// Checks that `who` can take a seat in a game with a bet of `bet` in `asset_id`, and
+		/// returns the part of the bet of the first player to give back to them if `who` bets
+		/// less.
+		fn check_play(
+			game_id: GameId,
+			game_state: &GameStateOf<T>,
+			who: &T::AccountId,
+			bet: BalanceOf<T>,
+			asset_id: AssetId,
+		) -> Result<BalanceOf<T>, Error<T>> {
+			ensure!(game_state.status != GameStatus::Settled, Error::<T>::InvalidGameStatus);
+			if let (Some(player1), None) = (&game_state.player1, &game_state.player2) {
+				ensure!(player1.player != *who, Error::<T>::CannotPlaySelf);
+			}
+			ensure!(!game_state.has_player(who), Error::<T>::PlayerAlreadyInGame);
+			ensure!(game_state.admits(who), Error::<T>::NotInvited);
+			ensure!(game_state.has_free_slots(), Error::<T>::GameIsFull);
+			ensure!(bet >= T::MinBet::get(), Error::<T>::BetTooLow);
+			ensure!(bet <= T::MaxBet::get(), Error::<T>::BetTooHigh);
+			match game_state.bet {
+				Some(game_bet) => {
+					let accepted = bet == game_bet ||
+						(bet < game_bet &&
+							<MinAccepts<T>>::get(game_id).map_or(false, |min| bet >= min));
+					ensure!(accepted && asset_id == game_state.asset_id, Error::<T>::StakeMismatch);
+					Ok(game_bet.saturating_sub(bet))
+				},
+				None => Ok(Zero::zero()),
+			}
+		}
+
+		/// Block until which players can act in a round starting now, given the reveal timeout
+		/// chosen for the game if any.
+		fn round_deadline(reveal_timeout: Option<T::BlockNumber>) -> T::BlockNumber {
+			<frame_system::Pallet<T>>::block_number()
+				.saturating_add(reveal_timeout.unwrap_or_else(T::RevealTimeout::get))
+		}
+
+		/// Block until which a second player can join a game created now.
+		fn join_deadline() -> T::BlockNumber {
+			<frame_system::Pallet<T>>::block_number().saturating_add(T::JoinTimeout::get())
+		}
+
+		/// Computes the result of a round whose movements are both revealed. Pays the game out
+		/// once it is decided or starts the next round otherwise.
+		///
+		/// The result is computed once, before any storage is written, and everything after works
+		/// from it, so the rules have no way to see the round half settled.
+		fn settle(game_id: GameId, game_state: &mut GameStateOf<T>) -> DispatchResult {
+			let movements = (
+				game_state.player1.as_ref().and_then(|p| p.movement),
+				game_state.player2.as_ref().and_then(|p| p.movement),
+			);
+			let round_result = match movements {
+				(Some(movement1), Some(movement2)) => movement1.play(&movement2),
+				_ => return Err(Error::<T>::GameNotReady.into()),
+			};
+			// Revealing checked the commitments already, so this only fails on corrupted storage.
+			let scheme_version = game_state.scheme_version;
+			let consistent = [&game_state.player1, &game_state.player2].into_iter().flatten().all(
+				|player_movement| {
+					player_movement.reveal_matches_commitment(game_id, scheme_version)
+				},
+			);
+			ensure!(consistent, Error::<T>::CommitmentMismatch);
+			game_state.record_round(&round_result, T::MaxRounds::get())?;
+			Self::deposit_event(Event::RoundFinished(game_id, round_result));
+
+			match game_state.decided_result() {
+				Some(GameResult::Draw)
+					if (game_state.replay_on_draw ||
+						T::DrawPolicy::get() == DrawPolicy::Replay ||
+						!T::AllowDraws::get()) &&
+						game_state.draw_replays < T::MaxDrawReplays::get() =>
+				{
+					game_state.replay_last_round();
+					game_state.reveal_deadline =
+						Some(Self::round_deadline(game_state.reveal_timeout));
+					Self::deposit_event(Event::RoundDrawn(game_id));
 					Ok(())
 				},
+				Some(game_result) =>
+					Self::finish_game(game_id, game_state, game_result, Permill::from_percent(100)),
+				None => {
+					game_state.start_next_round();
+					game_state.reveal_deadline =
+						Some(Self::round_deadline(game_state.reveal_timeout));
+					Ok(())
+				},
+			}
+		}
+
+		/// Records `game_result` and the winner it designates on a game, releases the winner's bet,
+		/// moves the `penalty` share of the loser's reserved bet minus the house fee to the winner,
+		/// returns the rest to the loser and emits the result. On a draw the `DrawPolicy` decides
+		/// whether the bets stay with the players or go to the house.
+		fn finish_game(
+			game_id: GameId,
+			game_state: &mut GameStateOf<T>,
+			game_result: GameResult,
+			penalty: Permill,
+		) -> DispatchResult {
+			let (player1, player2) = match (&game_state.player1, &game_state.player2) {
+				(Some(player1), Some(player2)) => (player1, player2),
+				_ => return Err(Error::<T>::GameNotReady.into()),
+			};
+			let asset_id = game_state.asset_id;
+			Self::remove_player_game(&player1.player, game_id);
+			Self::remove_player_game(&player2.player, game_id);
+			Self::record_head_to_head(&player1.player, &player2.player, &game_result);
+
+			let (winner, loser) = match game_result {
+				GameResult::Win => (Some(player1), Some(player2)),
+				GameResult::Lose => (Some(player2), Some(player1)),
+				GameResult::Draw | GameResult::NotPlayed => (None, None),
+			};
+			let (collected, prize) = if let (Some(winner), Some(loser)) = (winner, loser) {
+				T::Assets::unreserve(asset_id, &winner.player, winner.bet);
+				let lost = penalty * loser.bet;
+				let fee = T::HouseFee::get() * lost;
+				let collected = Self::collect_fee(asset_id, &loser.player, fee)?;
+				let stake = lost.saturating_sub(fee);
+				let missing =
+					T::Assets::repatriate_reserved(asset_id, &loser.player, &winner.player, stake)?;
+				let kept = loser.bet.saturating_sub(lost);
+				T::Assets::unreserve(
+					asset_id,
+					&loser.player,
+					kept.saturating_add(fee.saturating_sub(collected)),
+				);
+				if !collected.is_zero() {
+					Self::deposit_event(Event::FeeCollected(game_id, collected));
+				}
+				let released = stake.saturating_sub(missing);
+				Self::deposit_event(Event::StakeReleased(
+					game_id,
+					loser.player.clone(),
+					winner.player.clone(),
+					released,
+				));
+				let sponsored = Self::pay_sponsorship(game_id, asset_id, &winner.player)?;
+				let prize = released.saturating_add(sponsored);
+				<Wins<T>>::mutate(&winner.player, |wins| *wins = wins.saturating_add(1));
+				<SeasonWins<T>>::mutate(<CurrentSeason<T>>::get(), &winner.player, |wins| {
+					*wins = wins.saturating_add(1)
+				});
+				(collected, prize)
+			} else if game_result == GameResult::Draw &&
+				T::DrawPolicy::get() == DrawPolicy::HouseKeeps
+			{
+				let mut pot: BalanceOf<T> = Zero::zero();
+				for player_movement in [player1, player2] {
+					let collected =
+						Self::collect_fee(asset_id, &player_movement.player, player_movement.bet)?;
+					T::Assets::unreserve(
+						asset_id,
+						&player_movement.player,
+						player_movement.bet.saturating_sub(collected),
+					);
+					pot = pot.saturating_add(collected);
+				}
+				if !pot.is_zero() {
+					Self::deposit_event(Event::FeeCollected(game_id, pot));
+				}
+				Self::return_sponsorship(game_id, asset_id);
+				(pot, Zero::zero())
+			} else {
+				T::Assets::unreserve(asset_id, &player1.player, player1.bet);
+				T::Assets::unreserve(asset_id, &player2.player, player2.bet);
+				Self::return_sponsorship(game_id, asset_id);
+				(Zero::zero(), Zero::zero())
+			};
+			let winner = winner.map(|player_movement| player_movement.player.clone());
+
+			<OpenGames<T>>::mutate(|open_games| *open_games = open_games.saturating_sub(1));
+			game_state.pot = Zero::zero();
+			game_state.status = GameStatus::Settled;
+			game_state.game_result = game_result.clone();
+			game_state.winner = winner.clone();
+			Self::record_outcome(game_id, game_state, collected);
+
+			let event_seq = Self::next_event_seq(game_id);
+			Self::deposit_event(Event::GameFinished(
+				game_id,
+				game_result,
+				winner,
+				prize,
+				event_seq,
+			));
+			Ok(())
+		}
+
+		/// Moves `fee` of `asset_id` reserved by `who` to `FeeDestination` and returns the amount
+		/// collected. A fee that would leave `FeeDestination` below the existential deposit is
+		/// not collected and stays with `who`, so that it cannot hold up the settlement.
+		fn collect_fee(
+			asset_id: AssetId,
+			who: &T::AccountId,
+			fee: BalanceOf<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let destination = T::FeeDestination::get();
+			let balance = T::Assets::total_balance(asset_id, &destination).saturating_add(fee);
+			if fee.is_zero() || balance < T::Assets::minimum_balance(asset_id) {
+				return Ok(Zero::zero())
 			}
+			let missing = T::Assets::repatriate_reserved(asset_id, who, &destination, fee)?;
+			Ok(fee.saturating_sub(missing))
 		}
 	}
 }
@@ -0,0 +1,64 @@
+//! Storage migrations of the pallet.
+
+use crate::{game_logic::NATIVE_ASSET, Config, GameStateOf, Games, Pallet};
+use codec::{Decode, Encode};
+use frame_support::{
+	sp_std::{marker::PhantomData, vec::Vec},
+	storage::unhashed,
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+
+/// Tags the games stored before commitment scheme versions existed with version 0, so the
+/// `twox_64` commitments of games in flight keep verifying while new games use the current
+/// scheme, and gives them an empty round history, the native asset and no metadata. Only meant for
+/// chains whose existing commitments were built with `twox_64`.
+pub struct MigrateToV1<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let game_ids: Vec<_> = Games::<T>::iter_keys().collect();
+		let mut migrated = 0;
+		for game_id in &game_ids {
+			// `scheme_version`, `round_results`, `asset_id` and `metadata` are the last fields of
+			// a game, so appending version 0, an empty history, the native asset and no metadata
+			// to the old encoding gives the new one.
+			let raw = unhashed::get_raw(&Games::<T>::hashed_key_for(game_id));
+			if let Some(mut raw) = raw {
+				raw.extend([0, 0]);
+				raw.extend(NATIVE_ASSET.encode());
+				raw.push(0);
+				if let Ok(game_state) = GameStateOf::<T>::decode(&mut &raw[..]) {
+					Games::<T>::insert(game_id, game_state);
+					migrated += 1;
+				}
+			}
+		}
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		let reads = 2 * game_ids.len() as Weight + 1;
+		T::DbWeight::get().reads_writes(reads, migrated + 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		frame_support::ensure!(
+			Pallet::<T>::on_chain_storage_version() == 1,
+			"the storage version was not bumped"
+		);
+		// Games that fail to decode are skipped when iterating values.
+		frame_support::ensure!(
+			Games::<T>::iter_values().count() == Games::<T>::iter_keys().count(),
+			"some games were not tagged with a commitment scheme version"
+		);
+		frame_support::ensure!(
+			Pallet::<T>::open_games() == Pallet::<T>::count_open_games(),
+			"OpenGames does not match the number of unsettled games"
+		);
+		Ok(())
+	}
+}
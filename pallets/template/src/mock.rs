@@ -1,15 +1,35 @@
 use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
+use crate::{
+	game_logic::{AssetId, DrawPolicy, GameMode, NATIVE_ASSET},
+	multi_currency::{MultiReservableCurrency, NativeCurrency},
+};
+use frame_support::{
+	dispatch::DispatchResult,
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, ConstU8, GenesisBuild, Hooks, StorageMapShim},
+};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	DispatchError, Permill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+
+pub const FEE_DESTINATION: u64 = 99;
+
+/// Asset backed by `OtherBalances`, next to the native one backed by `Balances`.
+pub const OTHER_ASSET: AssetId = 1;
+
+pub const INITIAL_BALANCE: u64 = 1_000;
+
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
 	pub enum Test where
@@ -18,7 +38,9 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		OtherBalances: pallet_balances::<Instance2>::{Pallet, Call, Storage, Config<T>, Event<T>},
+		TemplateModule: pallet_template::{Pallet, Call, Storage, Config<T>, Event<T>},
 	}
 );
 
@@ -40,7 +62,7 @@ impl system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -49,11 +71,195 @@ impl system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_balances::Config<pallet_balances::Instance2> for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = StorageMapShim<
+		pallet_balances::Account<Test, pallet_balances::Instance2>,
+		system::Provider<Test>,
+		u64,
+		pallet_balances::AccountData<u64>,
+	>;
+	type WeightInfo = ();
+}
+
+/// Bets in the native asset go to `Balances` and bets in `OTHER_ASSET` to `OtherBalances`.
+pub struct MockAssets;
+
+impl MultiReservableCurrency<u64> for MockAssets {
+	type Balance = u64;
+
+	fn reserve(asset_id: AssetId, who: &u64, amount: u64) -> DispatchResult {
+		match asset_id {
+			OTHER_ASSET => NativeCurrency::<OtherBalances>::reserve(NATIVE_ASSET, who, amount),
+			_ => NativeCurrency::<Balances>::reserve(asset_id, who, amount),
+		}
+	}
+
+	fn ensure_can_reserve(asset_id: AssetId, who: &u64, amount: u64) -> DispatchResult {
+		match asset_id {
+			OTHER_ASSET =>
+				NativeCurrency::<OtherBalances>::ensure_can_reserve(NATIVE_ASSET, who, amount),
+			_ => NativeCurrency::<Balances>::ensure_can_reserve(asset_id, who, amount),
+		}
+	}
+
+	fn unreserve(asset_id: AssetId, who: &u64, amount: u64) -> u64 {
+		match asset_id {
+			OTHER_ASSET => NativeCurrency::<OtherBalances>::unreserve(NATIVE_ASSET, who, amount),
+			_ => NativeCurrency::<Balances>::unreserve(asset_id, who, amount),
+		}
+	}
+
+	fn repatriate_reserved(
+		asset_id: AssetId,
+		slashed: &u64,
+		beneficiary: &u64,
+		amount: u64,
+	) -> Result<u64, DispatchError> {
+		match asset_id {
+			OTHER_ASSET => NativeCurrency::<OtherBalances>::repatriate_reserved(
+				NATIVE_ASSET,
+				slashed,
+				beneficiary,
+				amount,
+			),
+			_ => NativeCurrency::<Balances>::repatriate_reserved(
+				asset_id,
+				slashed,
+				beneficiary,
+				amount,
+			),
+		}
+	}
+
+	fn minimum_balance(asset_id: AssetId) -> u64 {
+		match asset_id {
+			OTHER_ASSET => NativeCurrency::<OtherBalances>::minimum_balance(NATIVE_ASSET),
+			_ => NativeCurrency::<Balances>::minimum_balance(asset_id),
+		}
+	}
+
+	fn total_balance(asset_id: AssetId, who: &u64) -> u64 {
+		match asset_id {
+			OTHER_ASSET => NativeCurrency::<OtherBalances>::total_balance(NATIVE_ASSET, who),
+			_ => NativeCurrency::<Balances>::total_balance(asset_id, who),
+		}
+	}
+}
+
+parameter_types! {
+	pub static ExistentialDeposit: u64 = 1;
+	pub const HouseFee: Permill = Permill::from_percent(10);
+	pub const FeeDestination: u64 = FEE_DESTINATION;
+	pub static GameCreationFee: u64 = 0;
+	pub static GameDrawPolicy: DrawPolicy = DrawPolicy::SplitPot;
+	pub static AllowDraws: bool = true;
+	pub static MaxGamesPerBlockPerAccount: u32 = 5;
+	pub static MinBet: u64 = 1;
+	pub static MaxBet: u64 = 10_000;
+	pub static MaxGameLifetime: u64 = 1_000;
+	pub static RejectSharedSecret: bool = false;
+	pub static ForfeitPenalty: Permill = Permill::from_percent(100);
+	pub static MinRevealDelay: u64 = 0;
+}
+
 impl pallet_template::Config for Test {
 	type Event = Event;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type Assets = MockAssets;
+	type HouseFee = HouseFee;
+	type FeeDestination = FeeDestination;
+	type GameCreationFee = GameCreationFee;
+	type MinBet = MinBet;
+	type MaxBet = MaxBet;
+	type MinSecret = ConstU64<1_000>;
+	type MaxSecret = ConstU64<1_000_000>;
+	type MaxRounds = ConstU8<5>;
+	type RevealTimeout = ConstU64<10>;
+	type MinTimeout = ConstU64<3>;
+	type MaxTimeout = ConstU64<50>;
+	type JoinTimeout = ConstU64<5>;
+	type MaxGamesPerPlayer = ConstU32<3>;
+	type MaxDrawReplays = ConstU8<2>;
+	type DrawPolicy = GameDrawPolicy;
+	type AllowDraws = AllowDraws;
+	type MaxOpenGames = ConstU32<5>;
+	type MaxExpirySweep = ConstU32<2>;
+	type InactivityTimeout = ConstU64<8>;
+	type MaxGamesPerBlockPerAccount = MaxGamesPerBlockPerAccount;
+	type MaxBatch = ConstU32<4>;
+	type MaxGameLifetime = MaxGameLifetime;
+	type MaxOutcomes = ConstU32<2>;
+	type RejectSharedSecret = RejectSharedSecret;
+	type ForfeitPenalty = ForfeitPenalty;
+	type MinRevealDelay = MinRevealDelay;
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	new_test_ext_with_games(vec![])
+}
+
+/// Builds genesis storage with open games of the given modes and numbers of rounds.
+pub fn new_test_ext_with_games(initial_games: Vec<(GameMode, u8)>) -> sp_io::TestExternalities {
+	new_test_ext_with_genesis(pallet_template::GenesisConfig {
+		initial_games,
+		..Default::default()
+	})
+}
+
+/// Builds genesis storage with the given genesis configuration of the pallet.
+pub fn new_test_ext_with_genesis(
+	genesis: pallet_template::GenesisConfig<Test>,
+) -> sp_io::TestExternalities {
+	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![
+			(ALICE, INITIAL_BALANCE),
+			(BOB, INITIAL_BALANCE),
+			(CHARLIE, INITIAL_BALANCE),
+		],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	pallet_balances::GenesisConfig::<Test, pallet_balances::Instance2> {
+		balances: vec![
+			(ALICE, INITIAL_BALANCE),
+			(BOB, INITIAL_BALANCE),
+			(CHARLIE, INITIAL_BALANCE),
+		],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&genesis, &mut storage).unwrap();
+	let mut ext: sp_io::TestExternalities = storage.into();
+	// Events are not deposited on the genesis block.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		TemplateModule::on_initialize(System::block_number());
+	}
 }
@@ -0,0 +1,111 @@
+//! Currencies bets can be placed in, for chains with several assets.
+
+use crate::game_logic::{AssetId, NATIVE_ASSET};
+use frame_support::{
+	dispatch::DispatchResult,
+	sp_runtime::{traits::Zero, DispatchError, TokenError},
+	sp_std::marker::PhantomData,
+	traits::{BalanceStatus, Currency, ReservableCurrency},
+};
+
+/// Reservable balances held in several assets told apart by their [`AssetId`].
+pub trait MultiReservableCurrency<AccountId> {
+	/// Balance of an account in any of the assets.
+	type Balance;
+
+	/// Reserves `amount` of `asset_id` from the free balance of `who`.
+	fn reserve(asset_id: AssetId, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+	/// Fails the way `reserve` would if `amount` of `asset_id` cannot be reserved from `who`,
+	/// without reserving anything.
+	fn ensure_can_reserve(
+		asset_id: AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult;
+
+	/// Moves up to `amount` of `asset_id` reserved by `who` back to their free balance, returning
+	/// the part that could not be unreserved.
+	fn unreserve(asset_id: AssetId, who: &AccountId, amount: Self::Balance) -> Self::Balance;
+
+	/// Moves up to `amount` of `asset_id` reserved by `slashed` to the free balance of
+	/// `beneficiary`, returning the part that could not be moved.
+	fn repatriate_reserved(
+		asset_id: AssetId,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError>;
+
+	/// Smallest balance of `asset_id` an account can hold.
+	fn minimum_balance(asset_id: AssetId) -> Self::Balance;
+
+	/// Free and reserved balance of `asset_id` held by `who`.
+	fn total_balance(asset_id: AssetId, who: &AccountId) -> Self::Balance;
+}
+
+/// Exposes a single currency as the [`NATIVE_ASSET`], for chains without other assets.
+pub struct NativeCurrency<C>(PhantomData<C>);
+
+impl<AccountId, C: ReservableCurrency<AccountId>> MultiReservableCurrency<AccountId>
+	for NativeCurrency<C>
+{
+	type Balance = C::Balance;
+
+	fn reserve(asset_id: AssetId, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		ensure_native(asset_id)?;
+		C::reserve(who, amount)
+	}
+
+	fn ensure_can_reserve(
+		asset_id: AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		ensure_native(asset_id)?;
+		if C::can_reserve(who, amount) {
+			Ok(())
+		} else {
+			Err(DispatchError::Token(TokenError::NoFunds))
+		}
+	}
+
+	fn unreserve(asset_id: AssetId, who: &AccountId, amount: Self::Balance) -> Self::Balance {
+		if asset_id != NATIVE_ASSET {
+			return amount
+		}
+		C::unreserve(who, amount)
+	}
+
+	fn repatriate_reserved(
+		asset_id: AssetId,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		ensure_native(asset_id)?;
+		C::repatriate_reserved(slashed, beneficiary, amount, BalanceStatus::Free)
+	}
+
+	fn minimum_balance(asset_id: AssetId) -> Self::Balance {
+		if asset_id != NATIVE_ASSET {
+			return Zero::zero()
+		}
+		C::minimum_balance()
+	}
+
+	fn total_balance(asset_id: AssetId, who: &AccountId) -> Self::Balance {
+		if asset_id != NATIVE_ASSET {
+			return Zero::zero()
+		}
+		C::total_balance(who)
+	}
+}
+
+fn ensure_native(asset_id: AssetId) -> DispatchResult {
+	if asset_id == NATIVE_ASSET {
+		Ok(())
+	} else {
+		Err(DispatchError::Token(TokenError::UnknownAsset))
+	}
+}
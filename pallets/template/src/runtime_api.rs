@@ -0,0 +1,25 @@
+//! Runtime API exposing read-only queries over the games stored by the pallet.
+
+use crate::game_logic::{GameDetails, GameId, GameMovement, GameResult, Winner};
+use codec::Codec;
+use frame_support::{sp_std::vec::Vec, traits::Get};
+
+sp_api::decl_runtime_apis! {
+	pub trait RockPaperScissorsApi<AccountId, Balance, BlockNumber, MaxRounds> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+		MaxRounds: Get<u32>,
+	{
+		/// Ids of the unsettled games `account` takes part in, in ascending order.
+		fn games_for_account(account: AccountId) -> Vec<GameId>;
+
+		/// State of a game along with its status, the blocks left before its deadline and its
+		/// pot, `None` if it does not exist.
+		fn game_details(game_id: GameId) -> Option<GameDetails<AccountId, Balance, BlockNumber, MaxRounds>>;
+
+		/// Result and winner of a round where the first player plays `player1` and the second
+		/// `player2`.
+		fn preview_result(player1: GameMovement, player2: GameMovement) -> (GameResult, Winner);
+	}
+}
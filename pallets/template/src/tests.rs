@@ -1,20 +1,4580 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	game_logic::{
+		DrawPolicy, GameId, GameMetadata, GameMode, GameMovement, GameOutcome, GameResult,
+		GameState, GameStatus, MaxMetadataLen, PlayerDashboard, PlayerMovement, PlayerSlot, Secret,
+		SecretGameMovement, Winner, NATIVE_ASSET,
+	},
+	migrations::MigrateToV1,
+	mock::*,
+	weights::WeightInfo,
+	Error, Games,
+};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Get, GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_runtime::{traits::BadOrigin, Permill};
+
+const ALICE_SECRET: Secret = 1234;
+const BOB_SECRET: Secret = 5678;
+
+const BET_AMOUNT: u64 = 100;
+/// House fee taken from the loser's bet, 10% in the mock.
+const FEE: u64 = 10;
+const PRIZE: u64 = BET_AMOUNT - FEE;
+
+fn commit(
+	player: u64,
+	game_id: GameId,
+	movement: GameMovement,
+	secret: Secret,
+) -> SecretGameMovement {
+	SecretGameMovement::new(&movement, secret, game_id, &player)
+}
+
+/// Creates game 0 and has Alice and Bob commit to the given movements.
+fn create_full_game(alice_movement: GameMovement, bob_movement: GameMovement) {
+	assert_ok!(TemplateModule::create_game(
+		Origin::signed(ALICE),
+		GameMode::Classic,
+		1,
+		false,
+		None,
+		None,
+		None
+	));
+	assert_ok!(TemplateModule::play_game(
+		Origin::signed(ALICE),
+		0,
+		commit(ALICE, 0, alice_movement, ALICE_SECRET),
+		BET_AMOUNT,
+		NATIVE_ASSET,
+		None
+	));
+	assert_ok!(TemplateModule::play_game(
+		Origin::signed(BOB),
+		0,
+		commit(BOB, 0, bob_movement, BOB_SECRET),
+		BET_AMOUNT,
+		NATIVE_ASSET,
+		None
+	));
+}
+
+/// Creates an empty game at block `n`.
+fn create_game_at(n: u64) {
+	run_to_block(n);
+	assert_ok!(TemplateModule::create_game(
+		Origin::signed(BOB),
+		GameMode::Classic,
+		1,
+		false,
+		None,
+		None,
+		None
+	));
+}
+
+/// `game_state` with its volatile fields, `creator` and `created_at`, reset to their defaults, so
+/// tests can compare a game against a state built from `Default::default()` whoever created it
+/// and whenever.
+fn without_volatile_fields(game_state: crate::GameStateOf<Test>) -> crate::GameStateOf<Test> {
+	GameState { creator: None, created_at: 0, ..game_state }
+}
+
+/// Commits Alice and Bob to the given movements for the next round of game 0, unless they already
+/// did when joining, and reveals them. `round` keeps the secrets distinct between rounds.
+fn play_round(alice_movement: GameMovement, bob_movement: GameMovement, round: u64) {
+	let (alice_secret, bob_secret) = (ALICE_SECRET + round, BOB_SECRET + round);
+	if TemplateModule::games(0).unwrap().status == GameStatus::AwaitingMoves {
+		assert_ok!(TemplateModule::commit_move(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, alice_movement, alice_secret)
+		));
+		assert_ok!(TemplateModule::commit_move(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, bob_movement, bob_secret)
+		));
+	}
+	assert_ok!(TemplateModule::reveal_move(
+		Origin::signed(ALICE),
+		0,
+		alice_movement,
+		alice_secret,
+		true
+	));
+	assert_ok!(TemplateModule::reveal_move(Origin::signed(BOB), 0, bob_movement, bob_secret, true));
+}
+
+#[test]
+fn should_create_a_game() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!((game_state.creator, game_state.created_at), (Some(ALICE), 1));
+		assert_eq!(
+			without_volatile_fields(game_state),
+			GameState { reveal_deadline: Some(11), join_deadline: Some(6), ..Default::default() }
+		);
+		assert_eq!(TemplateModule::next_game_id(), 1);
+		System::assert_last_event(
+			crate::Event::<Test>::GameCreated(0, ALICE, GameMode::Classic, 1, 0, None).into(),
+		);
+	});
+}
+
+#[test]
+fn should_join_a_game() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		let secret_movement = commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET);
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			secret_movement.clone(),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(
+			game_state.player1,
+			Some(PlayerMovement::new(ALICE, secret_movement, BET_AMOUNT, 1))
+		);
+		assert!(game_state.has_free_slots());
+		System::assert_last_event(
+			crate::Event::<Test>::PlayerMadeMovement(0, ALICE, 1, BET_AMOUNT, 0).into(),
+		);
+	});
+}
+
+#[test]
+fn should_announce_remaining_slots() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		System::assert_last_event(
+			crate::Event::<Test>::PlayerMadeMovement(0, BOB, 0, 2 * BET_AMOUNT, 1).into(),
+		);
+	});
+}
+
+#[test]
+fn should_fail_to_join_twice_or_a_full_game() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(ALICE),
+				0,
+				commit(ALICE, 0, GameMovement::Rock, 0),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::GameNotFound
+		);
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(ALICE),
+				0,
+				commit(ALICE, 0, GameMovement::Rock, 0),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::PlayerAlreadyInGame
+		);
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(CHARLIE),
+				0,
+				commit(CHARLIE, 0, GameMovement::Rock, 0),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::GameIsFull
+		);
+	});
+}
+
+#[test]
+fn should_reveal_winner() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		assert_ok!(TemplateModule::reveal_winner(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			GameMovement::Scissors,
+			BOB_SECRET
+		));
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.game_result, GameResult::Win);
+		assert_eq!(game_state.winner, Some(ALICE));
+		System::assert_has_event(
+			crate::Event::<Test>::MoveRevealed(0, ALICE, GameMovement::Rock, 2).into(),
+		);
+		System::assert_has_event(
+			crate::Event::<Test>::MoveRevealed(0, BOB, GameMovement::Scissors, 3).into(),
+		);
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Win, Some(ALICE), PRIZE, 4).into(),
+		);
+	});
+}
+
+#[test]
+fn should_reveal_in_reverse_join_order() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		assert_noop!(
+			TemplateModule::reveal_winner(
+				Origin::signed(BOB),
+				0,
+				GameMovement::Rock,
+				ALICE_SECRET,
+				GameMovement::Scissors,
+				BOB_SECRET
+			),
+			Error::<Test>::InvalidHash
+		);
+		assert_ok!(TemplateModule::reveal_winner(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Scissors,
+			BOB_SECRET,
+			GameMovement::Rock,
+			ALICE_SECRET
+		));
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.game_result, GameResult::Win);
+		assert_eq!(game_state.winner, Some(ALICE));
+	});
+}
+
+#[test]
+fn should_reveal_moves_in_reverse_join_order() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Paper, GameMovement::Scissors);
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Scissors,
+			BOB_SECRET,
+			true
+		));
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Paper,
+			ALICE_SECRET,
+			true
+		));
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.game_result, GameResult::Lose);
+		assert_eq!(game_state.winner, Some(BOB));
+	});
+}
+
+#[test]
+fn should_reveal_moves_independently() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.player1.unwrap().movement, Some(GameMovement::Rock));
+		assert_eq!(game_state.player2.unwrap().movement, None);
+		assert_eq!(game_state.game_result, GameResult::NotPlayed);
+
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Paper,
+			BOB_SECRET,
+			true
+		));
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.game_result, GameResult::Lose);
+		assert_eq!(game_state.winner, Some(BOB));
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Lose, Some(BOB), PRIZE, 4).into(),
+		);
+	});
+}
+
+#[test]
+fn should_fail_to_reveal_move() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_noop!(
+			TemplateModule::reveal_move(
+				Origin::signed(ALICE),
+				0,
+				GameMovement::Rock,
+				ALICE_SECRET,
+				true
+			),
+			Error::<Test>::GameNotReady
+		);
+
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Paper, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_noop!(
+			TemplateModule::reveal_move(
+				Origin::signed(CHARLIE),
+				0,
+				GameMovement::Rock,
+				ALICE_SECRET,
+				true
+			),
+			Error::<Test>::PlayerNotInGame
+		);
+		assert_noop!(
+			TemplateModule::reveal_move(
+				Origin::signed(ALICE),
+				0,
+				GameMovement::Paper,
+				ALICE_SECRET,
+				true
+			),
+			Error::<Test>::InvalidHash
+		);
+
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		assert_noop!(
+			TemplateModule::reveal_move(
+				Origin::signed(ALICE),
+				0,
+				GameMovement::Rock,
+				ALICE_SECRET,
+				true
+			),
+			Error::<Test>::MoveAlreadyRevealed
+		);
+	});
+}
+
+#[test]
+fn should_reject_weak_secret() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, 1),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Paper, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_noop!(
+			TemplateModule::reveal_move(Origin::signed(ALICE), 0, GameMovement::Rock, 1, true),
+			Error::<Test>::WeakSecret
+		);
+		assert_noop!(
+			TemplateModule::reveal_winner(
+				Origin::signed(BOB),
+				0,
+				GameMovement::Paper,
+				BOB_SECRET,
+				GameMovement::Rock,
+				1
+			),
+			Error::<Test>::WeakSecret
+		);
+	});
+}
+
+#[test]
+fn should_reject_commitment_replayed_in_another_game() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		let replayed = TemplateModule::games(0).unwrap().player1.unwrap().secret_movement.unwrap();
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			1,
+			replayed,
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			1,
+			commit(BOB, 1, GameMovement::Paper, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_noop!(
+			TemplateModule::reveal_move(
+				Origin::signed(ALICE),
+				1,
+				GameMovement::Rock,
+				ALICE_SECRET,
+				true
+			),
+			Error::<Test>::InvalidHash
+		);
+	});
+}
+
+#[test]
+fn should_fail_to_reveal_winner_of_a_game_not_full() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_noop!(
+			TemplateModule::reveal_winner(
+				Origin::signed(ALICE),
+				0,
+				GameMovement::Rock,
+				ALICE_SECRET,
+				GameMovement::Paper,
+				BOB_SECRET
+			),
+			Error::<Test>::GameNotReady
+		);
+	});
+}
+
+#[test]
+fn should_record_winner_consistently_for_every_outcome() {
+	for (alice_movement, bob_movement, game_result, winner, prize) in [
+		(GameMovement::Rock, GameMovement::Scissors, GameResult::Win, Some(ALICE), PRIZE),
+		(GameMovement::Rock, GameMovement::Paper, GameResult::Lose, Some(BOB), PRIZE),
+		(GameMovement::Rock, GameMovement::Rock, GameResult::Draw, None, 0),
+	] {
+		new_test_ext().execute_with(|| {
+			create_full_game(alice_movement, bob_movement);
+			assert_ok!(TemplateModule::reveal_winner(
+				Origin::signed(ALICE),
+				0,
+				alice_movement,
+				ALICE_SECRET,
+				bob_movement,
+				BOB_SECRET
+			));
+
+			let game_state = TemplateModule::games(0).unwrap();
+			assert_eq!(game_state.game_result, game_result);
+			assert_eq!(game_state.winner, winner);
+			System::assert_last_event(
+				crate::Event::<Test>::GameFinished(0, game_result, winner, prize, 4).into(),
+			);
+		});
+	}
+}
+
+#[test]
+fn should_transition_game_status() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().status, GameStatus::Open);
+
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().status, GameStatus::Open);
+
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Paper, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().status, GameStatus::AwaitingReveal);
+
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().status, GameStatus::AwaitingReveal);
+
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Paper,
+			BOB_SECRET,
+			true
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().status, GameStatus::Settled);
+	});
+}
+
+#[test]
+fn should_reject_calls_into_a_settled_game() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		assert_ok!(TemplateModule::reveal_winner(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			GameMovement::Paper,
+			BOB_SECRET
+		));
+
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(CHARLIE),
+				0,
+				commit(CHARLIE, 0, GameMovement::Rock, 0),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::InvalidGameStatus
+		);
+		assert_noop!(
+			TemplateModule::reveal_move(
+				Origin::signed(ALICE),
+				0,
+				GameMovement::Rock,
+				ALICE_SECRET,
+				true
+			),
+			Error::<Test>::InvalidGameStatus
+		);
+	});
+}
+
+#[test]
+fn should_cancel_a_game() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_ok!(TemplateModule::cancel_game(Origin::signed(ALICE), 0));
+		assert_eq!(TemplateModule::games(0), None);
+		System::assert_last_event(crate::Event::<Test>::GameCancelled(0).into());
+	});
+}
+
+#[test]
+fn should_fail_to_cancel_a_game() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_noop!(
+			TemplateModule::cancel_game(Origin::signed(BOB), 0),
+			Error::<Test>::NotGameOwner
+		);
+
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Paper, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_noop!(
+			TemplateModule::cancel_game(Origin::signed(ALICE), 0),
+			Error::<Test>::GameIsFull
+		);
+	});
+}
+
+#[test]
+fn should_record_reveal_deadline_when_game_fills() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		assert_eq!(TemplateModule::games(0).unwrap().reveal_deadline, Some(11));
+	});
+}
+
+#[test]
+fn should_claim_timeout_over_an_unrevealed_opponent() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+
+		run_to_block(11);
+		assert_noop!(
+			TemplateModule::claim_timeout(Origin::signed(ALICE), 0),
+			Error::<Test>::RevealDeadlineNotReached
+		);
+
+		run_to_block(12);
+		assert_noop!(
+			TemplateModule::claim_timeout(Origin::signed(BOB), 0),
+			Error::<Test>::MoveNotRevealed
+		);
+		assert_noop!(
+			TemplateModule::claim_timeout(Origin::signed(CHARLIE), 0),
+			Error::<Test>::PlayerNotInGame
+		);
+		assert_ok!(TemplateModule::claim_timeout(Origin::signed(ALICE), 0));
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.status, GameStatus::Settled);
+		assert_eq!(game_state.winner, Some(ALICE));
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Win, Some(ALICE), PRIZE, 3).into(),
+		);
+	});
+}
+
+#[test]
+fn should_reserve_bet_when_joining() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		assert_eq!(Balances::reserved_balance(ALICE), BET_AMOUNT);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - BET_AMOUNT);
+		assert_eq!(TemplateModule::games(0).unwrap().player1.unwrap().bet, BET_AMOUNT);
+	});
+}
+
+#[test]
+fn should_fail_to_join_without_enough_balance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(ALICE),
+				0,
+				commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+				INITIAL_BALANCE + 1,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn should_return_bet_when_cancelling() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_ok!(TemplateModule::cancel_game(Origin::signed(ALICE), 0));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn should_pay_the_loser_bet_to_the_winner() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Paper, GameMovement::Rock);
+		assert_ok!(TemplateModule::reveal_winner(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Paper,
+			ALICE_SECRET,
+			GameMovement::Rock,
+			BOB_SECRET
+		));
+
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + PRIZE);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - BET_AMOUNT);
+		assert_eq!(Balances::free_balance(FEE_DESTINATION), FEE);
+		System::assert_has_event(crate::Event::<Test>::FeeCollected(0, FEE).into());
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn should_leave_a_fee_below_the_existential_deposit_with_the_loser() {
+	new_test_ext().execute_with(|| {
+		ExistentialDeposit::set(FEE + 1);
+		create_full_game(GameMovement::Paper, GameMovement::Rock);
+		assert_ok!(TemplateModule::reveal_winner(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Paper,
+			ALICE_SECRET,
+			GameMovement::Rock,
+			BOB_SECRET
+		));
+
+		assert_eq!(TemplateModule::games(0).unwrap().winner, Some(ALICE));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + PRIZE);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - PRIZE);
+		assert_eq!(Balances::free_balance(FEE_DESTINATION), 0);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			Event::TemplateModule(crate::Event::FeeCollected(..))
+		)));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn should_return_bets_on_a_draw() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Scissors, GameMovement::Scissors);
+		assert_ok!(TemplateModule::reveal_winner(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Scissors,
+			ALICE_SECRET,
+			GameMovement::Scissors,
+			BOB_SECRET
+		));
+
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(FEE_DESTINATION), 0);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn should_require_matching_bets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().bet, Some(BET_AMOUNT));
+
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(BOB),
+				0,
+				commit(BOB, 0, GameMovement::Paper, BOB_SECRET),
+				BET_AMOUNT + 1,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::StakeMismatch
+		);
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Paper, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().bet, Some(BET_AMOUNT));
+	});
+}
+
+#[test]
+fn should_reject_lizard_spock_movements_in_classic_games() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Lizard, GameMovement::Paper);
+		assert_noop!(
+			TemplateModule::reveal_move(
+				Origin::signed(ALICE),
+				0,
+				GameMovement::Lizard,
+				ALICE_SECRET,
+				true
+			),
+			Error::<Test>::InvalidMoveForMode
+		);
+		assert_noop!(
+			TemplateModule::reveal_winner(
+				Origin::signed(ALICE),
+				0,
+				GameMovement::Lizard,
+				ALICE_SECRET,
+				GameMovement::Paper,
+				BOB_SECRET
+			),
+			Error::<Test>::InvalidMoveForMode
+		);
+	});
+}
+
+#[test]
+fn should_play_a_lizard_spock_game() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::LizardSpock,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().mode, GameMode::LizardSpock);
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Lizard, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Spock, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Lizard,
+			ALICE_SECRET,
+			true
+		));
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Spock,
+			BOB_SECRET,
+			true
+		));
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.game_result, GameResult::Win);
+		assert_eq!(game_state.winner, Some(ALICE));
+	});
+}
+
+#[test]
+fn should_reject_invalid_number_of_rounds() {
+	new_test_ext().execute_with(|| {
+		for rounds in [0, 2, 7] {
+			assert_noop!(
+				TemplateModule::create_game(
+					Origin::signed(ALICE),
+					GameMode::Classic,
+					rounds,
+					false,
+					None,
+					None,
+					None
+				),
+				Error::<Test>::InvalidRounds
+			);
+		}
+	});
+}
+
+#[test]
+fn should_win_best_of_three_two_to_one() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			3,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Scissors, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!((game_state.player1_score, game_state.player2_score), (1, 0));
+		assert_eq!(game_state.status, GameStatus::AwaitingMoves);
+		assert_eq!(game_state.player1.unwrap().secret_movement, None);
+		System::assert_last_event(crate::Event::<Test>::RoundFinished(0, GameResult::Win).into());
+
+		play_round(GameMovement::Rock, GameMovement::Paper, 1);
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!((game_state.player1_score, game_state.player2_score), (1, 1));
+		assert_eq!(game_state.status, GameStatus::AwaitingMoves);
+
+		play_round(GameMovement::Paper, GameMovement::Rock, 2);
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!((game_state.player1_score, game_state.player2_score), (2, 1));
+		assert_eq!(game_state.status, GameStatus::Settled);
+		assert_eq!(game_state.winner, Some(ALICE));
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Win, Some(ALICE), PRIZE, 12).into(),
+		);
+	});
+}
+
+#[test]
+fn should_finish_best_of_three_once_decided() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			3,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Paper, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+
+		play_round(GameMovement::Rock, GameMovement::Paper, 0);
+		play_round(GameMovement::Scissors, GameMovement::Rock, 1);
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.rounds_played, 2);
+		assert_eq!(game_state.status, GameStatus::Settled);
+		assert_eq!(game_state.winner, Some(BOB));
+		assert_noop!(
+			TemplateModule::commit_move(
+				Origin::signed(ALICE),
+				0,
+				commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET)
+			),
+			Error::<Test>::InvalidGameStatus
+		);
+	});
+}
+
+#[test]
+fn should_list_the_games_of_an_account() {
+	new_test_ext().execute_with(|| {
+		for game_id in 0..4 {
+			assert_ok!(TemplateModule::create_game(
+				Origin::signed(ALICE),
+				GameMode::Classic,
+				1,
+				false,
+				None,
+				None,
+				None
+			));
+			let player = if game_id == 2 { CHARLIE } else { ALICE };
+			assert_ok!(TemplateModule::play_game(
+				Origin::signed(player),
+				game_id,
+				commit(player, game_id, GameMovement::Rock, ALICE_SECRET),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			));
+		}
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			3,
+			commit(BOB, 3, GameMovement::Paper, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+
+		assert_eq!(TemplateModule::games_for_account(&ALICE), vec![0, 1, 3]);
+		assert_eq!(TemplateModule::games_for_account(&BOB), vec![3]);
+		assert!(TemplateModule::games_for_account(&(ALICE + 1_000)).is_empty());
+	});
+}
+
+#[test]
+fn should_index_the_games_of_each_player() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		assert_eq!(TemplateModule::player_games(ALICE).into_inner(), vec![0]);
+		assert_eq!(TemplateModule::player_games(BOB).into_inner(), vec![0]);
+
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			1,
+			commit(ALICE, 1, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_eq!(TemplateModule::player_games(ALICE).into_inner(), vec![0, 1]);
+
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+		assert_eq!(TemplateModule::player_games(ALICE).into_inner(), vec![1]);
+		assert!(TemplateModule::player_games(BOB).is_empty());
+
+		assert_ok!(TemplateModule::cancel_game(Origin::signed(ALICE), 1));
+		assert!(TemplateModule::player_games(ALICE).is_empty());
+	});
+}
+
+#[test]
+fn should_limit_the_games_of_a_player() {
+	new_test_ext().execute_with(|| {
+		for game_id in 0..3 {
+			assert_ok!(TemplateModule::create_game(
+				Origin::signed(ALICE),
+				GameMode::Classic,
+				1,
+				false,
+				None,
+				None,
+				None
+			));
+			assert_ok!(TemplateModule::play_game(
+				Origin::signed(ALICE),
+				game_id,
+				commit(ALICE, game_id, GameMovement::Rock, ALICE_SECRET),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			));
+		}
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(ALICE),
+				3,
+				commit(ALICE, 3, GameMovement::Rock, ALICE_SECRET),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::TooManyGames
+		);
+		assert_eq!(Balances::reserved_balance(ALICE), 3 * BET_AMOUNT);
+	});
+}
+
+#[test]
+fn should_announce_the_creator_and_parameters_of_a_game() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(BOB),
+			GameMode::LizardSpock,
+			3,
+			false,
+			None,
+			None,
+			None
+		));
+		System::assert_last_event(
+			crate::Event::<Test>::GameCreated(0, BOB, GameMode::LizardSpock, 3, 0, None).into(),
+		);
+	});
+}
+
+#[test]
+fn should_seed_games_at_genesis() {
+	let initial_games = vec![(GameMode::Classic, 1), (GameMode::LizardSpock, 3)];
+	new_test_ext_with_games(initial_games).execute_with(|| {
+		assert_eq!(
+			TemplateModule::games(0),
+			Some(GameState {
+				reveal_deadline: Some(10),
+				join_deadline: Some(5),
+				..Default::default()
+			})
+		);
+		assert_eq!(
+			TemplateModule::games(1),
+			Some(GameState {
+				mode: GameMode::LizardSpock,
+				rounds: 3,
+				reveal_deadline: Some(10),
+				join_deadline: Some(5),
+				..Default::default()
+			})
+		);
+		assert_eq!(TemplateModule::next_game_id(), 2);
+
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert!(TemplateModule::games(2).is_some());
+	});
+}
+
+#[test]
+fn should_expire_open_games() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		create_game_at(2);
+		create_game_at(3);
+		create_game_at(4);
+
+		run_to_block(12);
+		assert_eq!(TemplateModule::games(0), None);
+		assert!(TemplateModule::games(1).is_some());
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert!(TemplateModule::player_games(ALICE).is_empty());
+		System::assert_has_event(crate::Event::<Test>::GameExpired(0).into());
+
+		// Game 1 lasts until block 12 and later games were created after it.
+		run_to_block(13);
+		assert_eq!(TemplateModule::games(1), None);
+		assert!(TemplateModule::games(2).is_some());
+
+		run_to_block(20);
+		assert_eq!(TemplateModule::next_game_id(), 4);
+		assert!((0..4).all(|game_id| TemplateModule::games(game_id).is_none()));
+		assert_eq!(TemplateModule::expiry_cursor(), 4);
+	});
+}
+
+#[test]
+fn should_not_expire_joined_games() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		run_to_block(20);
+		assert_eq!(TemplateModule::games(0).unwrap().status, GameStatus::AwaitingReveal);
+		assert_eq!(TemplateModule::expiry_cursor(), 1);
+	});
+}
+
+#[test]
+fn should_return_the_id_of_created_games() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			TemplateModule::do_create_game(&ALICE, GameMode::Classic, 1, false, None, None, None),
+			Ok(0)
+		);
+		assert_eq!(
+			TemplateModule::do_create_game(&BOB, GameMode::LizardSpock, 3, false, None, None, None),
+			Ok(1)
+		);
+		System::assert_last_event(
+			crate::Event::<Test>::GameCreated(1, BOB, GameMode::LizardSpock, 3, 0, None).into(),
+		);
+		assert_noop!(
+			TemplateModule::do_create_game(&ALICE, GameMode::Classic, 2, false, None, None, None),
+			Error::<Test>::InvalidRounds
+		);
+	});
+}
+
+#[test]
+fn should_not_wrap_game_ids() {
+	new_test_ext().execute_with(|| {
+		crate::NextGameId::<Test>::put(u64::MAX - 1);
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_eq!(TemplateModule::next_game_id(), u64::MAX);
+		assert_noop!(
+			TemplateModule::create_game(
+				Origin::signed(ALICE),
+				GameMode::Classic,
+				1,
+				false,
+				None,
+				None,
+				None
+			),
+			Error::<Test>::StorageOverflow
+		);
+		assert_eq!(TemplateModule::games(u64::MAX), None);
+	});
+}
+
+#[test]
+fn should_limit_open_games() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		for _ in 0..4 {
+			assert_ok!(TemplateModule::create_game(
+				Origin::signed(BOB),
+				GameMode::Classic,
+				1,
+				false,
+				None,
+				None,
+				None
+			));
+		}
+		assert_eq!(TemplateModule::open_games(), 5);
+		assert_noop!(
+			TemplateModule::create_game(
+				Origin::signed(BOB),
+				GameMode::Classic,
+				1,
+				false,
+				None,
+				None,
+				None
+			),
+			Error::<Test>::TooManyOpenGames
+		);
+
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+		assert_eq!(TemplateModule::open_games(), 4);
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(BOB),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			1,
+			commit(ALICE, 1, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_ok!(TemplateModule::cancel_game(Origin::signed(ALICE), 1));
+		assert_eq!(TemplateModule::open_games(), 4);
+
+		run_to_block(20);
+		assert_eq!(TemplateModule::open_games(), 0);
+	});
+}
+
+/// Creates game 0 with draw replays and has Alice and Bob join it with the given movements.
+fn create_replayed_game(alice_movement: GameMovement, bob_movement: GameMovement) {
+	assert_ok!(TemplateModule::create_game(
+		Origin::signed(ALICE),
+		GameMode::Classic,
+		1,
+		true,
+		None,
+		None,
+		None
+	));
+	assert_ok!(TemplateModule::play_game(
+		Origin::signed(ALICE),
+		0,
+		commit(ALICE, 0, alice_movement, ALICE_SECRET),
+		BET_AMOUNT,
+		NATIVE_ASSET,
+		None
+	));
+	assert_ok!(TemplateModule::play_game(
+		Origin::signed(BOB),
+		0,
+		commit(BOB, 0, bob_movement, BOB_SECRET),
+		BET_AMOUNT,
+		NATIVE_ASSET,
+		None
+	));
+}
+
+#[test]
+fn should_replay_a_drawn_game() {
+	new_test_ext().execute_with(|| {
+		create_replayed_game(GameMovement::Rock, GameMovement::Rock);
+		play_round(GameMovement::Rock, GameMovement::Rock, 0);
+		System::assert_last_event(crate::Event::<Test>::RoundDrawn(0).into());
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.status, GameStatus::AwaitingMoves);
+		assert_eq!(game_state.rounds_played, 0);
+		assert_eq!(game_state.draw_replays, 1);
+		assert_eq!(Balances::reserved_balance(ALICE), BET_AMOUNT);
+		assert_eq!(Balances::reserved_balance(BOB), BET_AMOUNT);
+
+		play_round(GameMovement::Rock, GameMovement::Paper, 1);
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.status, GameStatus::Settled);
+		assert_eq!(game_state.winner, Some(BOB));
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Lose, Some(BOB), PRIZE, 8).into(),
+		);
+	});
+}
+
+#[test]
+fn should_settle_a_draw_after_max_replays() {
+	new_test_ext().execute_with(|| {
+		create_replayed_game(GameMovement::Paper, GameMovement::Paper);
+		for round in 0..2 {
+			play_round(GameMovement::Paper, GameMovement::Paper, round);
+			System::assert_last_event(crate::Event::<Test>::RoundDrawn(0).into());
+		}
+		play_round(GameMovement::Paper, GameMovement::Paper, 2);
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.status, GameStatus::Settled);
+		assert_eq!(game_state.game_result, GameResult::Draw);
+		assert_eq!(game_state.draw_replays, 2);
+	});
+}
+
+#[test]
+fn should_only_let_the_invited_opponent_join_a_private_game() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			Some(BOB),
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(CHARLIE),
+				0,
+				commit(CHARLIE, 0, GameMovement::Paper, BOB_SECRET),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::NotInvited
+		);
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Paper, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().status, GameStatus::AwaitingReveal);
+	});
+}
+
+#[test]
+fn should_keep_the_first_slot_of_a_private_game_from_other_accounts() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			Some(BOB),
+			None,
+			None
+		));
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(CHARLIE),
+				0,
+				commit(CHARLIE, 0, GameMovement::Paper, BOB_SECRET),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::NotInvited
+		);
+		// The invited opponent may join first and leave the other slot to the creator.
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Paper, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(CHARLIE),
+				0,
+				commit(CHARLIE, 0, GameMovement::Paper, BOB_SECRET),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::NotInvited
+		);
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+	});
+}
+
+#[test]
+fn should_let_anyone_join_a_public_game() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(CHARLIE),
+			0,
+			commit(CHARLIE, 0, GameMovement::Paper, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+	});
+}
+
+#[test]
+fn should_not_play_against_oneself() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			Some(ALICE),
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(ALICE),
+				0,
+				commit(ALICE, 0, GameMovement::Paper, ALICE_SECRET),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::CannotPlaySelf
+		);
+	});
+}
+
+#[test]
+fn should_burn_the_game_creation_fee() {
+	new_test_ext().execute_with(|| {
+		GameCreationFee::set(5);
+		let total_issuance = Balances::total_issuance();
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 5);
+		assert_eq!(Balances::total_issuance(), total_issuance - 5);
+		System::assert_last_event(
+			crate::Event::<Test>::GameCreated(0, ALICE, GameMode::Classic, 1, 5, None).into(),
+		);
+	});
+}
+
+#[test]
+fn should_not_create_a_game_without_paying_the_fee() {
+	new_test_ext().execute_with(|| {
+		GameCreationFee::set(5);
+		assert_noop!(
+			TemplateModule::create_game(
+				Origin::signed(42),
+				GameMode::Classic,
+				1,
+				false,
+				None,
+				None,
+				None
+			),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+/// Creates a game in which `first` and then `second` join and reveal the given movements.
+fn play_single_round_game(
+	first: (u64, GameMovement, Secret),
+	second: (u64, GameMovement, Secret),
+) -> GameId {
+	let game_id = TemplateModule::next_game_id();
+	assert_ok!(TemplateModule::create_game(
+		Origin::signed(first.0),
+		GameMode::Classic,
+		1,
+		false,
+		None,
+		None,
+		None
+	));
+	for (player, movement, secret) in [first, second] {
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(player),
+			game_id,
+			commit(player, game_id, movement, secret),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+	}
+	for (player, movement, secret) in [first, second] {
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(player),
+			game_id,
+			movement,
+			secret,
+			true
+		));
+	}
+	game_id
+}
+
+#[test]
+fn should_track_head_to_head_records() {
+	new_test_ext().execute_with(|| {
+		let alice = |movement| (ALICE, movement, ALICE_SECRET);
+		let bob = |movement| (BOB, movement, BOB_SECRET);
+		play_single_round_game(alice(GameMovement::Rock), bob(GameMovement::Scissors));
+		play_single_round_game(bob(GameMovement::Paper), alice(GameMovement::Rock));
+		play_single_round_game(alice(GameMovement::Paper), bob(GameMovement::Paper));
+		play_single_round_game(bob(GameMovement::Rock), alice(GameMovement::Paper));
+
+		assert_eq!(TemplateModule::head_to_head(&ALICE, &BOB), (2, 1, 1));
+		assert_eq!(TemplateModule::head_to_head(&BOB, &ALICE), (1, 2, 1));
+		assert_eq!(TemplateModule::head_to_head(&ALICE, &CHARLIE), (0, 0, 0));
+		assert_eq!(crate::HeadToHeadRecords::<Test>::get((BOB, ALICE)), (0, 0, 0));
+	});
+}
+
+#[test]
+fn should_count_the_wins_of_each_player() {
+	new_test_ext().execute_with(|| {
+		let alice = |movement| (ALICE, movement, ALICE_SECRET);
+		let bob = |movement| (BOB, movement, BOB_SECRET);
+		play_single_round_game(alice(GameMovement::Rock), bob(GameMovement::Scissors));
+		play_single_round_game(bob(GameMovement::Rock), alice(GameMovement::Paper));
+		play_single_round_game(alice(GameMovement::Rock), bob(GameMovement::Paper));
+		play_single_round_game(alice(GameMovement::Scissors), bob(GameMovement::Scissors));
+
+		assert_eq!(TemplateModule::wins(ALICE), 2);
+		assert_eq!(TemplateModule::wins(BOB), 1);
+		assert_eq!(TemplateModule::wins(CHARLIE), 0);
+	});
+}
+
+#[test]
+fn should_not_count_draws_as_wins() {
+	new_test_ext().execute_with(|| {
+		play_single_round_game(
+			(ALICE, GameMovement::Paper, ALICE_SECRET),
+			(BOB, GameMovement::Paper, BOB_SECRET),
+		);
+		assert_eq!(TemplateModule::wins(ALICE), 0);
+		assert_eq!(TemplateModule::wins(BOB), 0);
+	});
+}
+
+#[test]
+fn should_pay_the_opponent_of_a_forfeiting_player() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Paper, GameMovement::Rock);
+		assert_noop!(
+			TemplateModule::forfeit(Origin::signed(CHARLIE), 0),
+			Error::<Test>::PlayerNotInGame
+		);
+		assert_ok!(TemplateModule::forfeit(Origin::signed(BOB), 0));
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.status, GameStatus::Settled);
+		assert_eq!(game_state.winner, Some(ALICE));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + PRIZE);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - BET_AMOUNT);
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Win, Some(ALICE), PRIZE, 2).into(),
+		);
+		assert_noop!(
+			TemplateModule::forfeit(Origin::signed(ALICE), 0),
+			Error::<Test>::InvalidGameStatus
+		);
+	});
+}
+
+#[test]
+fn should_only_take_the_forfeit_penalty_from_a_forfeiting_player() {
+	new_test_ext().execute_with(|| {
+		ForfeitPenalty::set(Permill::from_percent(50));
+		create_full_game(GameMovement::Paper, GameMovement::Rock);
+		assert_ok!(TemplateModule::forfeit(Origin::signed(BOB), 0));
+
+		// Half of the bet is lost, of which the house takes its fee.
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 45);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 50);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(FEE_DESTINATION), 5);
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Win, Some(ALICE), 45, 2).into(),
+		);
+	});
+}
+
+#[test]
+fn should_only_take_the_forfeit_penalty_from_a_timed_out_player() {
+	new_test_ext().execute_with(|| {
+		ForfeitPenalty::set(Permill::from_percent(50));
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		run_to_block(12);
+		assert_ok!(TemplateModule::claim_timeout(Origin::signed(ALICE), 0));
+
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 45);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 50);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn should_not_apply_the_forfeit_penalty_to_a_game_played_out() {
+	new_test_ext().execute_with(|| {
+		ForfeitPenalty::set(Permill::from_percent(50));
+		play_single_round_game(
+			(ALICE, GameMovement::Paper, ALICE_SECRET),
+			(BOB, GameMovement::Rock, BOB_SECRET),
+		);
+
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + PRIZE);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - BET_AMOUNT);
+	});
+}
+
+#[test]
+fn should_not_forfeit_a_game_nobody_joined() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_noop!(
+			TemplateModule::forfeit(Origin::signed(ALICE), 0),
+			Error::<Test>::GameNotReady
+		);
+	});
+}
+
+#[test]
+fn should_track_the_pot_of_a_game() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		assert_eq!(TemplateModule::games(0).unwrap().pot, 2 * BET_AMOUNT);
+
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+		assert_eq!(TemplateModule::games(0).unwrap().pot, 0);
+	});
+}
+
+#[test]
+fn should_let_anyone_settle_a_revealed_game() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Scissors,
+			BOB_SECRET,
+			false
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().status, GameStatus::AwaitingReveal);
+
+		assert_ok!(TemplateModule::settle_game(Origin::signed(CHARLIE), 0));
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.status, GameStatus::Settled);
+		assert_eq!(game_state.winner, Some(ALICE));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + PRIZE);
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Win, Some(ALICE), PRIZE, 4).into(),
+		);
+	});
+}
+
+#[test]
+fn should_fail_to_settle_a_game_not_revealed() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::settle_game(Origin::signed(CHARLIE), 0),
+			Error::<Test>::GameNotFound
+		);
+
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		assert_noop!(
+			TemplateModule::settle_game(Origin::signed(CHARLIE), 0),
+			Error::<Test>::GameNotReady
+		);
+
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		assert_noop!(
+			TemplateModule::settle_game(Origin::signed(CHARLIE), 0),
+			Error::<Test>::GameNotReady
+		);
+	});
+}
+
+#[test]
+fn should_fail_to_settle_a_game_twice() {
+	new_test_ext().execute_with(|| {
+		play_single_round_game(
+			(ALICE, GameMovement::Rock, ALICE_SECRET),
+			(BOB, GameMovement::Scissors, BOB_SECRET),
+		);
+		assert_noop!(
+			TemplateModule::settle_game(Origin::signed(CHARLIE), 0),
+			Error::<Test>::InvalidGameStatus
+		);
+	});
+}
+
+#[test]
+fn should_not_claim_timeout_once_both_revealed() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Scissors,
+			BOB_SECRET,
+			false
+		));
+
+		run_to_block(12);
+		assert_noop!(
+			TemplateModule::claim_timeout(Origin::signed(BOB), 0),
+			Error::<Test>::InvalidGameStatus
+		);
+	});
+}
+
+#[test]
+fn should_return_each_stake_on_a_draw_when_splitting_the_pot() {
+	new_test_ext().execute_with(|| {
+		GameDrawPolicy::set(DrawPolicy::SplitPot);
+		play_single_round_game(
+			(ALICE, GameMovement::Rock, ALICE_SECRET),
+			(BOB, GameMovement::Rock, BOB_SECRET),
+		);
+
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(FEE_DESTINATION), 0);
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Draw, None, 0, 4).into(),
+		);
+	});
+}
+
+#[test]
+fn should_send_the_pot_of_a_draw_to_the_house() {
+	new_test_ext().execute_with(|| {
+		GameDrawPolicy::set(DrawPolicy::HouseKeeps);
+		play_single_round_game(
+			(ALICE, GameMovement::Rock, ALICE_SECRET),
+			(BOB, GameMovement::Rock, BOB_SECRET),
+		);
+
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - BET_AMOUNT);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - BET_AMOUNT);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(FEE_DESTINATION), 2 * BET_AMOUNT);
+		System::assert_has_event(crate::Event::<Test>::FeeCollected(0, 2 * BET_AMOUNT).into());
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Draw, None, 0, 4).into(),
+		);
+	});
+}
+
+#[test]
+fn should_replay_every_draw_under_the_replay_policy() {
+	new_test_ext().execute_with(|| {
+		GameDrawPolicy::set(DrawPolicy::Replay);
+		create_full_game(GameMovement::Rock, GameMovement::Rock);
+		assert!(!TemplateModule::games(0).unwrap().replay_on_draw);
+
+		for round in 0..2 {
+			play_round(GameMovement::Rock, GameMovement::Rock, round);
+			System::assert_last_event(crate::Event::<Test>::RoundDrawn(0).into());
+		}
+		play_round(GameMovement::Rock, GameMovement::Rock, 2);
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.status, GameStatus::Settled);
+		assert_eq!(game_state.game_result, GameResult::Draw);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Draw, None, 0, 12).into(),
+		);
+	});
+}
+
+#[test]
+fn should_settle_when_the_last_player_reveals() {
+	for reveal_order in [[ALICE, BOB], [BOB, ALICE]] {
+		new_test_ext().execute_with(|| {
+			create_full_game(GameMovement::Scissors, GameMovement::Paper);
+			for player in reveal_order {
+				let (movement, secret) = if player == ALICE {
+					(GameMovement::Scissors, ALICE_SECRET)
+				} else {
+					(GameMovement::Paper, BOB_SECRET)
+				};
+				assert_eq!(TemplateModule::games(0).unwrap().status, GameStatus::AwaitingReveal);
+				assert_ok!(TemplateModule::reveal_move(
+					Origin::signed(player),
+					0,
+					movement,
+					secret,
+					true
+				));
+			}
+
+			let game_state = TemplateModule::games(0).unwrap();
+			assert_eq!(game_state.status, GameStatus::Settled);
+			assert_eq!(game_state.winner, Some(ALICE));
+			assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + PRIZE);
+			System::assert_last_event(
+				crate::Event::<Test>::GameFinished(0, GameResult::Win, Some(ALICE), PRIZE, 4)
+					.into(),
+			);
+		});
+	}
+}
+
+#[test]
+fn should_limit_the_games_created_by_an_account_in_a_block() {
+	new_test_ext().execute_with(|| {
+		MaxGamesPerBlockPerAccount::set(2);
+		for _ in 0..2 {
+			assert_ok!(TemplateModule::create_game(
+				Origin::signed(ALICE),
+				GameMode::Classic,
+				1,
+				false,
+				None,
+				None,
+				None
+			));
+		}
+		assert_eq!(TemplateModule::games_created_in_block(ALICE), 2);
+		assert_noop!(
+			TemplateModule::create_game(
+				Origin::signed(ALICE),
+				GameMode::Classic,
+				1,
+				false,
+				None,
+				None,
+				None
+			),
+			Error::<Test>::RateLimited
+		);
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(BOB),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+
+		run_to_block(2);
+		assert_eq!(TemplateModule::games_created_in_block(ALICE), 0);
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+	});
+}
+
+#[test]
+fn should_record_the_block_each_player_committed_in() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		run_to_block(4);
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Paper, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.player1.unwrap().committed_at, 1);
+		assert_eq!(game_state.player2.unwrap().committed_at, 4);
+	});
+}
+
+#[test]
+fn should_announce_each_revealed_move() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		System::assert_last_event(
+			crate::Event::<Test>::MoveRevealed(0, ALICE, GameMovement::Rock, 2).into(),
+		);
+
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Paper,
+			BOB_SECRET,
+			true
+		));
+		System::assert_has_event(
+			crate::Event::<Test>::MoveRevealed(0, BOB, GameMovement::Paper, 3).into(),
+		);
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Lose, Some(BOB), PRIZE, 4).into(),
+		);
+	});
+}
+
+#[test]
+fn should_bound_the_bets() {
+	new_test_ext().execute_with(|| {
+		MinBet::set(50);
+		MaxBet::set(200);
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(ALICE),
+				0,
+				commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+				49,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::BetTooLow
+		);
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(ALICE),
+				0,
+				commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+				201,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::BetTooHigh
+		);
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			200,
+			NATIVE_ASSET,
+			None
+		));
+
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(BOB),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			1,
+			commit(BOB, 1, GameMovement::Rock, BOB_SECRET),
+			50,
+			NATIVE_ASSET,
+			None
+		));
+	});
+}
+
+#[test]
+fn should_only_allow_free_games_without_a_minimum_bet() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(ALICE),
+				0,
+				commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+				0,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::BetTooLow
+		);
+
+		MinBet::set(0);
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			0,
+			NATIVE_ASSET,
+			None
+		));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+	});
+}
+
+#[test]
+fn should_purge_settled_games() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..3 {
+			play_single_round_game(
+				(ALICE, GameMovement::Rock, ALICE_SECRET),
+				(BOB, GameMovement::Scissors, BOB_SECRET),
+			);
+		}
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+
+		assert_noop!(TemplateModule::purge_settled(Origin::signed(ALICE), 3), BadOrigin);
+		assert_ok!(TemplateModule::purge_settled(Origin::root(), 2));
+		System::assert_last_event(crate::Event::<Test>::GamesPurged(2).into());
+		assert_eq!(Games::<Test>::iter().count(), 2);
+
+		assert_ok!(TemplateModule::purge_settled(Origin::root(), 10));
+		System::assert_last_event(crate::Event::<Test>::GamesPurged(1).into());
+		assert!((0..3).all(|game_id| TemplateModule::games(game_id).is_none()));
+		assert_eq!(TemplateModule::games(3).unwrap().status, GameStatus::Open);
+	});
+}
+
+#[test]
+fn should_only_reveal_while_the_reveal_window_is_open() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		assert_noop!(
+			TemplateModule::set_reveal_window(Origin::signed(BOB), 0, false),
+			Error::<Test>::NotGameOwner
+		);
+		assert_ok!(TemplateModule::set_reveal_window(Origin::signed(ALICE), 0, false));
+		System::assert_last_event(crate::Event::<Test>::RevealWindowSet(0, false).into());
+
+		run_to_block(30);
+		assert_noop!(
+			TemplateModule::reveal_move(
+				Origin::signed(ALICE),
+				0,
+				GameMovement::Rock,
+				ALICE_SECRET,
+				true
+			),
+			Error::<Test>::RevealNotOpen
+		);
+		assert_noop!(
+			TemplateModule::reveal_winner(
+				Origin::signed(ALICE),
+				0,
+				GameMovement::Rock,
+				ALICE_SECRET,
+				GameMovement::Paper,
+				BOB_SECRET
+			),
+			Error::<Test>::RevealNotOpen
+		);
+
+		assert_ok!(TemplateModule::set_reveal_window(Origin::signed(ALICE), 0, true));
+		assert_eq!(TemplateModule::games(0).unwrap().reveal_deadline, Some(40));
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Paper,
+			BOB_SECRET,
+			true
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().winner, Some(BOB));
+
+		assert_noop!(
+			TemplateModule::set_reveal_window(Origin::signed(ALICE), 0, false),
+			Error::<Test>::InvalidGameStatus
+		);
+	});
+}
+
+#[test]
+fn should_not_close_the_reveal_window_once_a_player_revealed() {
+	new_test_ext().execute_with(|| {
+		// Bob created the game and would lose it once he reveals.
+		create_game_at(1);
+		for (player, movement, secret) in
+			[(ALICE, GameMovement::Rock, ALICE_SECRET), (BOB, GameMovement::Scissors, BOB_SECRET)]
+		{
+			assert_ok!(TemplateModule::play_game(
+				Origin::signed(player),
+				0,
+				commit(player, 0, movement, secret),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			));
+		}
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		for open in [false, true] {
+			assert_noop!(
+				TemplateModule::set_reveal_window(Origin::signed(BOB), 0, open),
+				Error::<Test>::MoveAlreadyRevealed
+			);
+		}
+
+		run_to_block(12);
+		assert_ok!(TemplateModule::claim_timeout(Origin::signed(ALICE), 0));
+		assert_eq!(TemplateModule::games(0).unwrap().winner, Some(ALICE));
+	});
+}
+
+#[test]
+fn should_not_extend_the_deadline_when_reopening_an_open_reveal_window() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		assert_eq!(TemplateModule::games(0).unwrap().reveal_deadline, Some(11));
+
+		run_to_block(5);
+		assert_ok!(TemplateModule::set_reveal_window(Origin::signed(ALICE), 0, true));
+		assert_eq!(TemplateModule::games(0).unwrap().reveal_deadline, Some(11));
+
+		run_to_block(12);
+		assert_ok!(TemplateModule::resolve_stalemate(Origin::signed(BOB), 0));
+	});
+}
+
+#[test]
+fn should_claim_timeout_after_a_custom_reveal_timeout() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			Some(3),
+			None
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().reveal_deadline, Some(11));
+		for (player, movement, secret) in
+			[(ALICE, GameMovement::Rock, ALICE_SECRET), (BOB, GameMovement::Paper, BOB_SECRET)]
+		{
+			assert_ok!(TemplateModule::play_game(
+				Origin::signed(player),
+				0,
+				commit(player, 0, movement, secret),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			));
+		}
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+
+		run_to_block(4);
+		assert_noop!(
+			TemplateModule::claim_timeout(Origin::signed(ALICE), 0),
+			Error::<Test>::RevealDeadlineNotReached
+		);
+		run_to_block(5);
+		assert_ok!(TemplateModule::claim_timeout(Origin::signed(ALICE), 0));
+		assert_eq!(TemplateModule::games(0).unwrap().winner, Some(ALICE));
+	});
+}
+
+#[test]
+fn should_clamp_custom_reveal_timeouts() {
+	new_test_ext().execute_with(|| {
+		for (reveal_timeout, clamped) in [(1, 3), (1_000, 50)] {
+			let game_id = TemplateModule::next_game_id();
+			assert_ok!(TemplateModule::create_game(
+				Origin::signed(ALICE),
+				GameMode::Classic,
+				1,
+				false,
+				None,
+				Some(reveal_timeout),
+				None
+			));
+			let game_state = TemplateModule::games(game_id).unwrap();
+			assert_eq!(game_state.reveal_timeout, Some(clamped));
+		}
+	});
+}
+
+#[test]
+fn should_describe_a_game_in_one_call() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TemplateModule::game_details(0), None);
+
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		run_to_block(4);
+		let details = TemplateModule::game_details(0).unwrap();
+		assert_eq!(details.game_state, TemplateModule::games(0).unwrap());
+		assert_eq!(details.status, GameStatus::AwaitingReveal);
+		assert_eq!(details.blocks_until_timeout, Some(7));
+		assert_eq!(details.pot, 2 * BET_AMOUNT);
+
+		run_to_block(20);
+		assert_eq!(TemplateModule::game_details(0).unwrap().blocks_until_timeout, Some(0));
+
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+		let details = TemplateModule::game_details(0).unwrap();
+		assert_eq!(details.status, GameStatus::Settled);
+		assert_eq!(details.blocks_until_timeout, None);
+		assert_eq!(details.pot, 0);
+	});
+}
+
+#[test]
+fn should_wait_for_both_players_to_request_a_rematch() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		assert_noop!(
+			TemplateModule::request_rematch(Origin::signed(ALICE), 0),
+			Error::<Test>::InvalidGameStatus
+		);
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+
+		assert_noop!(
+			TemplateModule::request_rematch(Origin::signed(CHARLIE), 0),
+			Error::<Test>::PlayerNotInGame
+		);
+		assert_ok!(TemplateModule::request_rematch(Origin::signed(ALICE), 0));
+		assert_eq!(TemplateModule::rematch_requests(0), Some(ALICE));
+		assert_noop!(
+			TemplateModule::request_rematch(Origin::signed(ALICE), 0),
+			Error::<Test>::RematchAlreadyRequested
+		);
+		assert_eq!(TemplateModule::next_game_id(), 1);
+		assert_eq!(TemplateModule::rematches(0), None);
+	});
+}
+
+#[test]
+fn should_create_a_rematch_once_both_players_requested_it() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+		assert_ok!(TemplateModule::request_rematch(Origin::signed(ALICE), 0));
+		assert_ok!(TemplateModule::request_rematch(Origin::signed(BOB), 0));
+		System::assert_last_event(crate::Event::<Test>::RematchCreated(0, 1).into());
+		assert_eq!(TemplateModule::rematch_requests(0), None);
+		assert_eq!(TemplateModule::rematches(0), Some(1));
+
+		let game_state = TemplateModule::games(1).unwrap();
+		assert_eq!(game_state.status, GameStatus::AwaitingMoves);
+		assert_eq!(game_state.creator, Some(BOB));
+		assert_eq!(game_state.pot, 2 * BET_AMOUNT);
+		for player_movement in [game_state.player1.unwrap(), game_state.player2.unwrap()] {
+			assert!(!player_movement.has_committed());
+			assert_eq!(player_movement.bet, BET_AMOUNT);
+		}
+		assert_eq!(Balances::reserved_balance(ALICE), BET_AMOUNT);
+		assert_eq!(Balances::reserved_balance(BOB), BET_AMOUNT);
+		assert_eq!(TemplateModule::games_for_account(&BOB), vec![1]);
+		assert_noop!(
+			TemplateModule::request_rematch(Origin::signed(ALICE), 0),
+			Error::<Test>::RematchAlreadyRequested
+		);
+
+		for (player, movement, secret) in
+			[(ALICE, GameMovement::Paper, ALICE_SECRET), (BOB, GameMovement::Rock, BOB_SECRET)]
+		{
+			assert_ok!(TemplateModule::commit_move(
+				Origin::signed(player),
+				1,
+				commit(player, 1, movement, secret)
+			));
+		}
+		for (player, movement, secret) in
+			[(ALICE, GameMovement::Paper, ALICE_SECRET), (BOB, GameMovement::Rock, BOB_SECRET)]
+		{
+			assert_ok!(TemplateModule::reveal_move(
+				Origin::signed(player),
+				1,
+				movement,
+				secret,
+				true
+			));
+		}
+		assert_eq!(TemplateModule::games(1).unwrap().winner, Some(ALICE));
+	});
+}
+
+#[test]
+fn should_only_accept_secrets_within_bounds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		for (player, secret) in [(ALICE, 1_000_001), (BOB, 1_000_000)] {
+			assert_ok!(TemplateModule::play_game(
+				Origin::signed(player),
+				0,
+				commit(player, 0, GameMovement::Rock, secret),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			));
+		}
+		assert_noop!(
+			TemplateModule::reveal_move(
+				Origin::signed(ALICE),
+				0,
+				GameMovement::Rock,
+				1_000_001,
+				true
+			),
+			Error::<Test>::InvalidSecret
+		);
+		assert_noop!(
+			TemplateModule::reveal_winner(
+				Origin::signed(BOB),
+				0,
+				GameMovement::Rock,
+				1_000_000,
+				GameMovement::Rock,
+				1_000_001
+			),
+			Error::<Test>::InvalidSecret
+		);
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Rock,
+			1_000_000,
+			true
+		));
+	});
+}
+
+#[test]
+fn should_create_a_batch_of_games() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(BOB),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::create_games_batch(
+			Origin::signed(ALICE),
+			GameMode::LizardSpock,
+			3,
+			4
+		));
+		System::assert_last_event(crate::Event::<Test>::GamesCreatedBatch(1, 4).into());
+
+		assert_eq!(TemplateModule::next_game_id(), 5);
+		for game_id in 1..5 {
+			let game_state = TemplateModule::games(game_id).unwrap();
+			assert_eq!(game_state.mode, GameMode::LizardSpock);
+			assert_eq!(game_state.rounds, 3);
+			assert_eq!(game_state.creator, Some(ALICE));
+		}
+	});
+}
+
+#[test]
+fn should_create_all_of_a_batch_or_nothing() {
+	new_test_ext().execute_with(|| {
+		for count in [0, 5] {
+			assert_noop!(
+				TemplateModule::create_games_batch(
+					Origin::signed(ALICE),
+					GameMode::Classic,
+					1,
+					count
+				),
+				Error::<Test>::InvalidBatchSize
+			);
+		}
+
+		crate::NextGameId::<Test>::put(u64::MAX - 2);
+		assert_noop!(
+			TemplateModule::create_games_batch(Origin::signed(ALICE), GameMode::Classic, 1, 3),
+			Error::<Test>::StorageOverflow
+		);
+
+		crate::NextGameId::<Test>::put(0);
+		MaxGamesPerBlockPerAccount::set(2);
+		assert_noop!(
+			TemplateModule::create_games_batch(Origin::signed(ALICE), GameMode::Classic, 1, 3),
+			Error::<Test>::RateLimited
+		);
+		assert_eq!(TemplateModule::games(0), None);
+	});
+}
+
+#[test]
+fn should_not_settle_a_movement_that_no_longer_matches_its_commitment() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		// Corrupt the movement Alice revealed.
+		Games::<Test>::mutate(0, |game_state| {
+			game_state.as_mut().unwrap().player1.as_mut().unwrap().movement =
+				Some(GameMovement::Paper);
+		});
+
+		assert_noop!(
+			TemplateModule::reveal_move(
+				Origin::signed(BOB),
+				0,
+				GameMovement::Scissors,
+				BOB_SECRET,
+				true
+			),
+			Error::<Test>::CommitmentMismatch
+		);
+	});
+}
+
+#[test]
+fn should_recommit_while_alone_in_a_game() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_noop!(
+			TemplateModule::recommit(
+				Origin::signed(BOB),
+				0,
+				commit(BOB, 0, GameMovement::Paper, BOB_SECRET)
+			),
+			Error::<Test>::PlayerNotInGame
+		);
+
+		run_to_block(3);
+		let secret_movement = commit(ALICE, 0, GameMovement::Paper, ALICE_SECRET);
+		assert_ok!(TemplateModule::recommit(Origin::signed(ALICE), 0, secret_movement.clone()));
+		let player_movement = TemplateModule::games(0).unwrap().player1.unwrap();
+		assert_eq!(player_movement.secret_movement, Some(secret_movement));
+		assert_eq!(player_movement.committed_at, 3);
+		assert_eq!(Balances::reserved_balance(ALICE), BET_AMOUNT);
+
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Rock, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Paper,
+			ALICE_SECRET,
+			true
+		));
+	});
+}
+
+#[test]
+fn should_not_recommit_once_the_opponent_joined() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		assert_noop!(
+			TemplateModule::recommit(
+				Origin::signed(ALICE),
+				0,
+				commit(ALICE, 0, GameMovement::Scissors, ALICE_SECRET)
+			),
+			Error::<Test>::GameIsFull
+		);
+	});
+}
+
+#[test]
+fn should_replay_draws_when_draws_are_not_allowed() {
+	new_test_ext().execute_with(|| {
+		AllowDraws::set(false);
+		create_full_game(GameMovement::Paper, GameMovement::Paper);
+		assert!(!TemplateModule::games(0).unwrap().replay_on_draw);
+
+		play_round(GameMovement::Paper, GameMovement::Paper, 0);
+		System::assert_last_event(crate::Event::<Test>::RoundDrawn(0).into());
+		play_round(GameMovement::Paper, GameMovement::Scissors, 1);
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.status, GameStatus::Settled);
+		assert_eq!(game_state.winner, Some(BOB));
+		assert_eq!(game_state.draw_replays, 1);
+	});
+}
+
+#[test]
+fn should_fall_back_to_the_draw_policy_once_replays_run_out() {
+	new_test_ext().execute_with(|| {
+		AllowDraws::set(false);
+		GameDrawPolicy::set(DrawPolicy::HouseKeeps);
+		create_full_game(GameMovement::Paper, GameMovement::Paper);
+		for round in 0..2 {
+			play_round(GameMovement::Paper, GameMovement::Paper, round);
+			System::assert_last_event(crate::Event::<Test>::RoundDrawn(0).into());
+		}
+		play_round(GameMovement::Paper, GameMovement::Paper, 2);
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.status, GameStatus::Settled);
+		assert_eq!(game_state.game_result, GameResult::Draw);
+		assert_eq!(Balances::free_balance(FEE_DESTINATION), 2 * BET_AMOUNT);
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Draw, None, 0, 12).into(),
+		);
+	});
+}
+
+#[test]
+fn should_expose_the_result_of_a_game() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TemplateModule::result_of(0), None);
+
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		assert_eq!(TemplateModule::result_of(0), Some((GameResult::NotPlayed, None)));
+
+		play_round(GameMovement::Rock, GameMovement::Paper, 0);
+		assert_eq!(TemplateModule::result_of(0), Some((GameResult::Lose, Some(BOB))));
+	});
+}
+
+#[test]
+fn should_keep_blocked_accounts_out_of_games() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_noop!(TemplateModule::block_account(Origin::signed(ALICE), BOB), BadOrigin);
+		assert_ok!(TemplateModule::block_account(Origin::root(), BOB));
+		System::assert_last_event(crate::Event::<Test>::AccountBlocked(BOB).into());
+
+		assert_noop!(
+			TemplateModule::create_game(
+				Origin::signed(BOB),
+				GameMode::Classic,
+				1,
+				false,
+				None,
+				None,
+				None
+			),
+			Error::<Test>::AccountBlocked
+		);
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(BOB),
+				0,
+				commit(BOB, 0, GameMovement::Rock, BOB_SECRET),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::AccountBlocked
+		);
+
+		assert_ok!(TemplateModule::unblock_account(Origin::root(), BOB));
+		System::assert_last_event(crate::Event::<Test>::AccountUnblocked(BOB).into());
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(BOB),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Rock, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Paper, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		play_round(GameMovement::Paper, GameMovement::Rock, 0);
+		assert_ok!(TemplateModule::request_rematch(Origin::signed(BOB), 0));
+		assert_ok!(TemplateModule::block_account(Origin::root(), BOB));
+		assert_noop!(
+			TemplateModule::request_rematch(Origin::signed(ALICE), 0),
+			Error::<Test>::AccountBlocked
+		);
+	});
+}
+
+#[test]
+fn should_charge_only_the_lookup_when_revealing_a_finished_game() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		let post_info = TemplateModule::reveal_winner(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			GameMovement::Paper,
+			BOB_SECRET,
+		)
+		.unwrap();
+		assert_eq!(post_info.actual_weight, None);
+
+		let events = System::events().len();
+		let error = TemplateModule::reveal_winner(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			GameMovement::Paper,
+			BOB_SECRET,
+		)
+		.unwrap_err();
+		assert_eq!(error.error, Error::<Test>::GameAlreadyFinished.into());
+		let lookup = <Test as frame_system::Config>::DbWeight::get().reads(1);
+		assert_eq!(error.post_info.actual_weight, Some(lookup));
+		assert!(lookup < <() as WeightInfo>::reveal_winner());
+		assert_eq!(System::events().len(), events);
+		assert_eq!(TemplateModule::event_seq(0), 5);
+	});
+}
+
+#[test]
+fn should_cancel_inactive_games_while_weight_remains() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(TemplateModule::create_game(
+				Origin::signed(ALICE),
+				GameMode::Classic,
+				1,
+				false,
+				None,
+				None,
+				None
+			));
+		}
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		// The games are inactive from block 10 and would expire at block 12.
+		create_game_at(10);
+
+		let per_game = <() as WeightInfo>::cancel_inactive_game();
+		assert_eq!(TemplateModule::on_idle(10, per_game - 1), 0);
+		assert_eq!(TemplateModule::open_games(), 4);
+
+		assert_eq!(TemplateModule::on_idle(10, 2 * per_game), 2 * per_game);
+		assert!(!Games::<Test>::contains_key(0));
+		assert!(!Games::<Test>::contains_key(1));
+		assert!(Games::<Test>::contains_key(2));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		System::assert_last_event(crate::Event::<Test>::GameCancelled(1).into());
+
+		assert_eq!(TemplateModule::on_idle(10, Weight::MAX), per_game);
+		assert!(!Games::<Test>::contains_key(2));
+		assert!(Games::<Test>::contains_key(3));
+		assert_eq!(TemplateModule::open_games(), 1);
+	});
+}
+
+#[test]
+fn should_claim_a_no_show_win_after_the_join_deadline() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_noop!(
+			TemplateModule::claim_no_show(Origin::signed(ALICE), 0),
+			Error::<Test>::PlayerNotInGame
+		);
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+
+		run_to_block(6);
+		assert_noop!(
+			TemplateModule::claim_no_show(Origin::signed(ALICE), 0),
+			Error::<Test>::JoinDeadlineNotReached
+		);
+
+		run_to_block(7);
+		assert_noop!(
+			TemplateModule::claim_no_show(Origin::signed(BOB), 0),
+			Error::<Test>::PlayerNotInGame
+		);
+		assert_ok!(TemplateModule::claim_no_show(Origin::signed(ALICE), 0));
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.status, GameStatus::Settled);
+		assert_eq!(game_state.game_result, GameResult::Win);
+		assert_eq!(game_state.winner, Some(ALICE));
+		assert_eq!(TemplateModule::wins(ALICE), 0);
+		assert_eq!(TemplateModule::open_games(), 0);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Win, Some(ALICE), 0, 1).into(),
+		);
+	});
+}
+
+#[test]
+fn should_not_cancel_or_recommit_after_a_no_show_claim() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		run_to_block(7);
+		assert_ok!(TemplateModule::claim_no_show(Origin::signed(ALICE), 0));
+
+		// A bet reserved in another game must not be released by cancelling the claimed one.
+		create_game_at(7);
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			1,
+			commit(ALICE, 1, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_noop!(
+			TemplateModule::cancel_game(Origin::signed(ALICE), 0),
+			Error::<Test>::InvalidGameStatus
+		);
+		assert_noop!(
+			TemplateModule::recommit(
+				Origin::signed(ALICE),
+				0,
+				commit(ALICE, 0, GameMovement::Paper, ALICE_SECRET)
+			),
+			Error::<Test>::InvalidGameStatus
+		);
+		assert_eq!(Balances::reserved_balance(ALICE), BET_AMOUNT);
+		assert_eq!(TemplateModule::open_games(), 1);
+	});
+}
+
+#[test]
+fn should_verify_commitments_under_the_scheme_of_the_game() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		Games::<Test>::mutate(0, |game_state| game_state.as_mut().unwrap().scheme_version = 0);
+		assert_noop!(
+			TemplateModule::reveal_move(
+				Origin::signed(ALICE),
+				0,
+				GameMovement::Rock,
+				ALICE_SECRET,
+				true
+			),
+			Error::<Test>::InvalidHash
+		);
+
+		for (player, movement, secret) in
+			[(ALICE, GameMovement::Rock, ALICE_SECRET), (BOB, GameMovement::Paper, BOB_SECRET)]
+		{
+			let legacy = SecretGameMovement::with_scheme(0, &movement, secret, 0, &player).unwrap();
+			Games::<Test>::mutate(0, |game_state| {
+				let player_movement = game_state.as_mut().unwrap().player_movement_mut(&player);
+				player_movement.unwrap().secret_movement = Some(legacy);
+			});
+		}
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Paper,
+			BOB_SECRET,
+			true
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().winner, Some(BOB));
+	});
+}
+
+#[test]
+fn should_tag_games_stored_before_the_migration_with_scheme_version_0() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		let game_state = TemplateModule::games(0).unwrap();
+		// Games stored before the migration lack the trailing scheme version, round history,
+		// asset and metadata.
+		let key = Games::<Test>::hashed_key_for(0);
+		let encoded = game_state.encode();
+		frame_support::storage::unhashed::put_raw(&key, &encoded[..encoded.len() - 7]);
+		StorageVersion::new(0).put::<TemplateModule>();
+		assert_eq!(TemplateModule::games(0), None);
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(TemplateModule::games(0), Some(GameState { scheme_version: 0, ..game_state }));
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+	});
+}
+
+#[test]
+fn should_record_the_result_of_every_round() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TemplateModule::round_history(0), None);
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			3,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Scissors, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_eq!(TemplateModule::round_history(0), Some(vec![]));
+
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+		assert_eq!(TemplateModule::round_history(0), Some(vec![GameResult::Win]));
+		play_round(GameMovement::Rock, GameMovement::Rock, 1);
+		play_round(GameMovement::Rock, GameMovement::Paper, 2);
+		assert_eq!(
+			TemplateModule::round_history(0),
+			Some(vec![GameResult::Win, GameResult::Draw, GameResult::Lose])
+		);
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.winner_of_round(0), Some(&ALICE));
+		assert_eq!(game_state.winner_of_round(1), None);
+		assert_eq!(game_state.winner_of_round(2), Some(&BOB));
+		assert_eq!(game_state.winner_of_round(3), None);
+	});
+}
+
+#[test]
+fn should_bet_and_pay_out_in_the_asset_of_the_game() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(ALICE),
+				0,
+				commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+				BET_AMOUNT,
+				7,
+				None
+			),
+			Error::<Test>::UnknownAsset
+		);
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			OTHER_ASSET,
+			None
+		));
+		assert_eq!(OtherBalances::reserved_balance(ALICE), BET_AMOUNT);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(TemplateModule::games(0).unwrap().asset_id, OTHER_ASSET);
+
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(BOB),
+				0,
+				commit(BOB, 0, GameMovement::Scissors, BOB_SECRET),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			),
+			Error::<Test>::StakeMismatch
+		);
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Scissors, BOB_SECRET),
+			BET_AMOUNT,
+			OTHER_ASSET,
+			None
+		));
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+
+		assert_eq!(OtherBalances::free_balance(ALICE), INITIAL_BALANCE + PRIZE);
+		assert_eq!(OtherBalances::free_balance(BOB), INITIAL_BALANCE - BET_AMOUNT);
+		assert_eq!(OtherBalances::free_balance(FEE_DESTINATION), FEE);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn should_number_the_events_of_a_game_in_order() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		assert_eq!(TemplateModule::event_seq(0), 2);
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+
+		System::assert_has_event(
+			crate::Event::<Test>::PlayerMadeMovement(0, ALICE, 1, BET_AMOUNT, 0).into(),
+		);
+		System::assert_has_event(
+			crate::Event::<Test>::PlayerMadeMovement(0, BOB, 0, 2 * BET_AMOUNT, 1).into(),
+		);
+		System::assert_has_event(
+			crate::Event::<Test>::MoveRevealed(0, ALICE, GameMovement::Rock, 2).into(),
+		);
+		System::assert_has_event(
+			crate::Event::<Test>::MoveRevealed(0, BOB, GameMovement::Scissors, 3).into(),
+		);
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Win, Some(ALICE), PRIZE, 4).into(),
+		);
+		assert_eq!(TemplateModule::event_seq(0), 5);
+		assert_eq!(TemplateModule::event_seq(1), 0);
+	});
+}
+
+#[test]
+fn should_preview_the_result_of_every_classic_round() {
+	use GameMovement::*;
+	let cases = [
+		(Rock, Rock, GameResult::Draw, Winner::Nobody),
+		(Rock, Paper, GameResult::Lose, Winner::Player2),
+		(Rock, Scissors, GameResult::Win, Winner::Player1),
+		(Paper, Rock, GameResult::Win, Winner::Player1),
+		(Paper, Paper, GameResult::Draw, Winner::Nobody),
+		(Paper, Scissors, GameResult::Lose, Winner::Player2),
+		(Scissors, Rock, GameResult::Lose, Winner::Player2),
+		(Scissors, Paper, GameResult::Win, Winner::Player1),
+		(Scissors, Scissors, GameResult::Draw, Winner::Nobody),
+	];
+	new_test_ext().execute_with(|| {
+		for (player1, player2, game_result, winner) in cases {
+			assert_eq!(TemplateModule::preview_result(player1, player2), (game_result, winner));
+		}
+		assert_eq!(TemplateModule::next_game_id(), 0);
+		assert!(System::events().is_empty());
+	});
+}
+
+#[test]
+fn should_store_the_metadata_of_a_game() {
+	new_test_ext().execute_with(|| {
+		let metadata: GameMetadata = b"finals".to_vec().try_into().unwrap();
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			Some(metadata.clone())
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().metadata, Some(metadata.clone()));
+		System::assert_last_event(
+			crate::Event::<Test>::GameCreated(0, ALICE, GameMode::Classic, 1, 0, Some(metadata))
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn should_not_decode_metadata_longer_than_the_bound() {
+	let max_len = MaxMetadataLen::get() as usize;
+	let call = |metadata| crate::Call::<Test>::create_game {
+		mode: GameMode::Classic,
+		rounds: 1,
+		replay_on_draw: false,
+		opponent: None,
+		reveal_timeout: None,
+		metadata,
+	};
+	let longest = call(Some(vec![0; max_len].try_into().unwrap())).encode();
+	assert!(crate::Call::<Test>::decode(&mut &longest[..]).is_ok());
+
+	// The metadata is the last argument, so an unbounded one can replace the encoded `None`.
+	let mut too_long = call(None).encode();
+	too_long.pop();
+	too_long.extend(Some(vec![0u8; max_len + 1]).encode());
+	assert!(crate::Call::<Test>::decode(&mut &too_long[..]).is_err());
+}
+
+#[test]
+fn should_settle_the_ready_games_of_a_batch_and_skip_the_others() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Scissors,
+			BOB_SECRET,
+			false
+		));
+		// Game 1 has no players yet and game 2 does not exist.
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(BOB),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+
+		let game_ids = vec![1, 0, 2].try_into().unwrap();
+		assert_ok!(TemplateModule::settle_games_batch(Origin::signed(CHARLIE), game_ids));
+		assert_eq!(TemplateModule::games(0).unwrap().status, GameStatus::Settled);
+		assert_eq!(TemplateModule::games(0).unwrap().winner, Some(ALICE));
+		assert_eq!(TemplateModule::games(1).unwrap().status, GameStatus::Open);
+		System::assert_last_event(crate::Event::<Test>::BatchSettled(1, 2).into());
+
+		// Game 0 is already settled, so nothing is left to settle.
+		let game_ids = vec![0, 1].try_into().unwrap();
+		assert_ok!(TemplateModule::settle_games_batch(Origin::signed(CHARLIE), game_ids));
+		System::assert_last_event(crate::Event::<Test>::BatchSettled(0, 2).into());
+		assert_noop!(
+			TemplateModule::settle_games_batch(Origin::signed(CHARLIE), Default::default()),
+			Error::<Test>::InvalidBatchSize
+		);
+	});
+}
+
+#[test]
+fn should_report_the_slot_each_player_joined_in() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		System::assert_has_event(
+			crate::Event::<Test>::PlayerJoined(0, ALICE, PlayerSlot::Player1).into(),
+		);
+		System::assert_has_event(
+			crate::Event::<Test>::PlayerJoined(0, BOB, PlayerSlot::Player2).into(),
+		);
+	});
+}
+
+#[test]
+fn should_refund_both_players_of_a_stalemate() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		assert_eq!(Balances::reserved_balance(ALICE), BET_AMOUNT);
+		assert_eq!(Balances::reserved_balance(BOB), BET_AMOUNT);
+
+		run_to_block(11);
+		assert_noop!(
+			TemplateModule::resolve_stalemate(Origin::signed(ALICE), 0),
+			Error::<Test>::RevealDeadlineNotReached
+		);
+
+		run_to_block(12);
+		assert_noop!(
+			TemplateModule::resolve_stalemate(Origin::signed(CHARLIE), 0),
+			Error::<Test>::PlayerNotInGame
+		);
+		assert_ok!(TemplateModule::resolve_stalemate(Origin::signed(BOB), 0));
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.status, GameStatus::Settled);
+		assert_eq!(game_state.game_result, GameResult::Draw);
+		assert_eq!(game_state.winner, None);
+		assert_eq!(game_state.pot, 0);
+		for player in [ALICE, BOB] {
+			assert_eq!(Balances::reserved_balance(player), 0);
+			assert_eq!(Balances::free_balance(player), INITIAL_BALANCE);
+			assert!(TemplateModule::games_for_account(&player).is_empty());
+		}
+		assert_eq!(TemplateModule::open_games(), 0);
+		System::assert_last_event(crate::Event::<Test>::GameAbandoned(0).into());
+		assert_noop!(
+			TemplateModule::resolve_stalemate(Origin::signed(ALICE), 0),
+			Error::<Test>::InvalidGameStatus
+		);
+	});
+}
+
+#[test]
+fn should_not_resolve_a_stalemate_once_a_player_revealed() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+
+		run_to_block(12);
+		assert_noop!(
+			TemplateModule::resolve_stalemate(Origin::signed(BOB), 0),
+			Error::<Test>::MoveAlreadyRevealed
+		);
+	});
+}
+
+#[test]
+fn should_expire_open_games_behind_a_long_custom_timeout() {
+	new_test_ext().execute_with(|| {
+		for creator in [ALICE, BOB] {
+			assert_ok!(TemplateModule::create_game(
+				Origin::signed(creator),
+				GameMode::Classic,
+				1,
+				false,
+				None,
+				Some(50),
+				None
+			));
+		}
+		run_to_block(2);
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(CHARLIE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		// Game 1 is in play, so its own timeout applies.
+		for (player, movement, secret) in
+			[(ALICE, GameMovement::Rock, ALICE_SECRET), (BOB, GameMovement::Paper, BOB_SECRET)]
+		{
+			assert_ok!(TemplateModule::play_game(
+				Origin::signed(player),
+				1,
+				commit(player, 1, movement, secret),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			));
+		}
+		assert_eq!(TemplateModule::games(1).unwrap().reveal_deadline, Some(52));
+
+		run_to_block(12);
+		assert_eq!(TemplateModule::games(0), None);
+		System::assert_has_event(crate::Event::<Test>::GameExpired(0).into());
+		assert!(TemplateModule::games(2).is_some());
+
+		run_to_block(13);
+		assert_eq!(TemplateModule::games(2), None);
+		System::assert_has_event(crate::Event::<Test>::GameExpired(2).into());
+		assert_eq!(TemplateModule::games(1).unwrap().status, GameStatus::AwaitingReveal);
+	});
+}
+
+#[test]
+fn should_refund_a_rematch_nobody_committed_to() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+		assert_ok!(TemplateModule::request_rematch(Origin::signed(ALICE), 0));
+		assert_ok!(TemplateModule::request_rematch(Origin::signed(BOB), 0));
+		assert_eq!(TemplateModule::games(1).unwrap().reveal_deadline, Some(11));
+		let (alice_balance, bob_balance) =
+			(Balances::free_balance(ALICE), Balances::free_balance(BOB));
+
+		assert_noop!(
+			TemplateModule::resolve_stalemate(Origin::signed(BOB), 1),
+			Error::<Test>::RevealDeadlineNotReached
+		);
+
+		run_to_block(12);
+		assert_ok!(TemplateModule::resolve_stalemate(Origin::signed(BOB), 1));
+		let game_state = TemplateModule::games(1).unwrap();
+		assert_eq!(game_state.status, GameStatus::Settled);
+		assert_eq!(game_state.game_result, GameResult::Draw);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + BET_AMOUNT);
+		assert_eq!(Balances::free_balance(BOB), bob_balance + BET_AMOUNT);
+		System::assert_last_event(crate::Event::<Test>::GameAbandoned(1).into());
+	});
+}
+
+#[test]
+fn should_not_resolve_a_stalemate_once_a_round_was_won() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			3,
+			false,
+			None,
+			None,
+			None
+		));
+		for (player, movement, secret) in
+			[(ALICE, GameMovement::Rock, ALICE_SECRET), (BOB, GameMovement::Scissors, BOB_SECRET)]
+		{
+			assert_ok!(TemplateModule::play_game(
+				Origin::signed(player),
+				0,
+				commit(player, 0, movement, secret),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			));
+		}
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+		assert_eq!(TemplateModule::games(0).unwrap().reveal_deadline, Some(11));
+
+		run_to_block(12);
+		assert_noop!(
+			TemplateModule::resolve_stalemate(Origin::signed(BOB), 0),
+			Error::<Test>::RoundAlreadyWon
+		);
+
+		assert_ok!(TemplateModule::commit_move(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Paper, ALICE_SECRET + 1)
+		));
+		assert_ok!(TemplateModule::claim_timeout(Origin::signed(ALICE), 0));
+		assert_eq!(TemplateModule::games(0).unwrap().winner, Some(ALICE));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + PRIZE);
+	});
+}
+
+#[test]
+fn should_recompute_the_open_games_and_stakes_from_storage() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		create_game_at(1);
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(CHARLIE),
+			1,
+			commit(CHARLIE, 1, GameMovement::Paper, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_eq!(TemplateModule::count_open_games(), 2);
+		assert_eq!(TemplateModule::count_open_games(), TemplateModule::open_games());
+		assert_eq!(TemplateModule::total_at_stake(), 3 * BET_AMOUNT);
+		assert_eq!(
+			TemplateModule::total_at_stake(),
+			[ALICE, BOB, CHARLIE]
+				.iter()
+				.map(|who| Balances::reserved_balance(*who))
+				.sum::<u64>()
+		);
+
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+		assert_eq!(TemplateModule::count_open_games(), 1);
+		assert_eq!(TemplateModule::total_at_stake(), BET_AMOUNT);
+
+		crate::OpenGames::<Test>::put(2);
+		assert_ne!(TemplateModule::count_open_games(), TemplateModule::open_games());
+	});
+}
+
+#[test]
+fn should_number_genesis_games_from_the_first_game_id_and_seed_wins() {
+	let genesis = crate::GenesisConfig {
+		initial_games: vec![(GameMode::Classic, 1), (GameMode::LizardSpock, 3)],
+		first_game_id: 10,
+		wins: vec![(ALICE, 3), (BOB, 1)],
+	};
+	new_test_ext_with_genesis(genesis).execute_with(|| {
+		assert_eq!(TemplateModule::games(0), None);
+		assert_eq!(
+			TemplateModule::games(10).map(|game_state| game_state.mode),
+			Some(GameMode::Classic)
+		);
+		assert_eq!(
+			TemplateModule::games(11).map(|game_state| game_state.mode),
+			Some(GameMode::LizardSpock)
+		);
+		assert_eq!(TemplateModule::next_game_id(), 12);
+		assert_eq!(TemplateModule::open_games(), 2);
+		assert_eq!((TemplateModule::wins(ALICE), TemplateModule::wins(BOB)), (3, 1));
+
+		run_to_block(11);
+		assert_eq!(TemplateModule::games(10), None);
+		assert_eq!(TemplateModule::games(11), None);
+		assert_eq!(TemplateModule::expiry_cursor(), 12);
+
+		create_game_at(11);
+		assert!(TemplateModule::games(12).is_some());
+	});
+}
+
+#[test]
+fn should_emit_the_canonical_events_of_a_game() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+
+		let events: Vec<_> = System::events()
+			.into_iter()
+			.filter_map(|record| match record.event {
+				Event::TemplateModule(event) => Some(event),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(
+			events,
+			vec![
+				crate::Event::GameCreated(0, ALICE, GameMode::Classic, 1, 0, None),
+				crate::Event::PlayerJoined(0, ALICE, PlayerSlot::Player1),
+				crate::Event::PlayerMadeMovement(0, ALICE, 1, BET_AMOUNT, 0),
+				crate::Event::PlayerJoined(0, BOB, PlayerSlot::Player2),
+				crate::Event::PlayerMadeMovement(0, BOB, 0, 2 * BET_AMOUNT, 1),
+				crate::Event::MoveRevealed(0, ALICE, GameMovement::Rock, 2),
+				crate::Event::MoveRevealed(0, BOB, GameMovement::Scissors, 3),
+				crate::Event::RoundFinished(0, GameResult::Win),
+				crate::Event::FeeCollected(0, FEE),
+				crate::Event::StakeReleased(0, BOB, ALICE, PRIZE),
+				crate::Event::GameFinished(0, GameResult::Win, Some(ALICE), PRIZE, 4),
+			]
+		);
+	});
+}
+
+#[test]
+fn should_join_with_a_lower_bet_down_to_the_min_accept() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::set_min_accept(Origin::signed(ALICE), 0, 50));
+		System::assert_last_event(crate::Event::<Test>::MinAcceptSet(0, 50).into());
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Scissors, BOB_SECRET),
+			60,
+			NATIVE_ASSET,
+			None
+		));
+
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!((game_state.bet, game_state.pot), (Some(60), 120));
+		assert_eq!(game_state.player1.map(|player_movement| player_movement.bet), Some(60));
+		assert_eq!(Balances::reserved_balance(ALICE), 60);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 60);
+		assert_eq!(TemplateModule::min_accept(0), None);
+		System::assert_has_event(
+			crate::Event::<Test>::PlayerMadeMovement(0, BOB, 0, 120, 1).into(),
+		);
+
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 54);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 60);
+	});
+}
+
+#[test]
+fn should_only_accept_lower_bets_within_the_min_accept() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		let join = |bet| {
+			TemplateModule::play_game(
+				Origin::signed(BOB),
+				0,
+				commit(BOB, 0, GameMovement::Scissors, BOB_SECRET),
+				bet,
+				NATIVE_ASSET,
+				None,
+			)
+		};
+		assert_noop!(join(60), Error::<Test>::StakeMismatch);
+
+		assert_noop!(
+			TemplateModule::set_min_accept(Origin::signed(BOB), 0, 50),
+			Error::<Test>::NotGameOwner
+		);
+		assert_ok!(TemplateModule::set_min_accept(Origin::signed(ALICE), 0, 50));
+		assert_noop!(join(40), Error::<Test>::StakeMismatch);
+		assert_noop!(join(BET_AMOUNT + 1), Error::<Test>::StakeMismatch);
+		assert_ok!(join(50));
+
+		assert_noop!(
+			TemplateModule::set_min_accept(Origin::signed(ALICE), 0, 10),
+			Error::<Test>::InvalidGameStatus
+		);
+	});
+}
+
+#[test]
+fn should_return_the_bets_when_revealing_past_the_game_lifetime() {
+	new_test_ext().execute_with(|| {
+		MaxGameLifetime::set(5);
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+
+		run_to_block(6);
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!(game_state.status, GameStatus::AwaitingReveal);
+		assert!(game_state.player1.unwrap().is_revealed());
+
+		run_to_block(7);
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Scissors,
+			BOB_SECRET,
+			true
+		));
+		System::assert_last_event(crate::Event::<Test>::GameExpired(0).into());
+		let game_state = TemplateModule::games(0).unwrap();
+		assert_eq!((game_state.status, game_state.winner), (GameStatus::Settled, None));
+		assert!(!game_state.player2.unwrap().is_revealed());
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(TemplateModule::open_games(), 0);
+	});
+}
+
+#[test]
+fn should_not_reveal_both_movements_past_the_game_lifetime() {
+	new_test_ext().execute_with(|| {
+		MaxGameLifetime::set(5);
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+
+		run_to_block(7);
+		assert_ok!(TemplateModule::reveal_winner(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			GameMovement::Scissors,
+			BOB_SECRET
+		));
+		System::assert_last_event(crate::Event::<Test>::GameExpired(0).into());
+		assert_eq!(TemplateModule::games(0).unwrap().game_result, GameResult::Draw);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn should_only_join_a_game_created_by_the_expected_creator() {
+	new_test_ext().execute_with(|| {
+		create_game_at(1);
+		assert_noop!(
+			TemplateModule::play_game(
+				Origin::signed(ALICE),
+				0,
+				commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				Some(CHARLIE)
+			),
+			Error::<Test>::CreatorMismatch
+		);
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Rock, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			Some(BOB)
+		));
+		assert!(TemplateModule::games(0).unwrap().has_player(&ALICE));
+	});
+}
+
+#[test]
+fn should_report_the_stake_released_to_the_winner() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Scissors, GameMovement::Rock);
+		play_round(GameMovement::Scissors, GameMovement::Rock, 0);
+
+		System::assert_has_event(crate::Event::<Test>::StakeReleased(0, ALICE, BOB, PRIZE).into());
+		System::assert_last_event(
+			crate::Event::<Test>::GameFinished(0, GameResult::Lose, Some(BOB), PRIZE, 4).into(),
+		);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + PRIZE);
+	});
+}
+
+#[test]
+fn should_keep_the_outcome_of_a_game_after_it_is_purged() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Scissors);
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+		assert_ok!(TemplateModule::purge_settled(Origin::root(), 10));
+		assert_eq!(TemplateModule::games(0), None);
+
+		assert_eq!(
+			TemplateModule::outcome(0),
+			Some(GameOutcome {
+				player1: Some((ALICE, Some(GameMovement::Rock), BET_AMOUNT)),
+				player2: Some((BOB, Some(GameMovement::Scissors), BET_AMOUNT)),
+				game_result: GameResult::Win,
+				winner: Some(ALICE),
+				fee: FEE,
+				settled_at: 1,
+				season: 0,
+			})
+		);
+	});
+}
+
+#[test]
+fn should_only_keep_the_latest_outcomes() {
+	new_test_ext().execute_with(|| {
+		let alice = (ALICE, GameMovement::Rock, ALICE_SECRET);
+		let bob = (BOB, GameMovement::Scissors, BOB_SECRET);
+		let game_ids: Vec<_> = (0..3).map(|_| play_single_round_game(alice, bob)).collect();
+
+		assert_eq!(TemplateModule::outcome(game_ids[0]), None);
+		assert!(TemplateModule::outcome(game_ids[1]).is_some());
+		assert!(TemplateModule::outcome(game_ids[2]).is_some());
+		assert_eq!(crate::OutcomeQueue::<Test>::get().into_inner(), game_ids[1..].to_vec());
+	});
+}
+
+#[test]
+fn should_reject_a_secret_shared_by_both_players_when_configured() {
+	new_test_ext().execute_with(|| {
+		RejectSharedSecret::set(true);
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		for (player, movement) in [(ALICE, GameMovement::Rock), (BOB, GameMovement::Scissors)] {
+			assert_ok!(TemplateModule::play_game(
+				Origin::signed(player),
+				0,
+				commit(player, 0, movement, ALICE_SECRET),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			));
+		}
+		assert_noop!(
+			TemplateModule::reveal_winner(
+				Origin::signed(ALICE),
+				0,
+				GameMovement::Rock,
+				ALICE_SECRET,
+				GameMovement::Scissors,
+				ALICE_SECRET
+			),
+			Error::<Test>::SharedSecret
+		);
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		assert_noop!(
+			TemplateModule::reveal_move(
+				Origin::signed(BOB),
+				0,
+				GameMovement::Scissors,
+				ALICE_SECRET,
+				true
+			),
+			Error::<Test>::SharedSecret
+		);
+
+		RejectSharedSecret::set(false);
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Scissors,
+			ALICE_SECRET,
+			true
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().winner, Some(ALICE));
+	});
+}
+
+#[test]
+fn should_bound_the_storage_taken_by_a_game() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			5,
+			false,
+			Some(BOB),
+			None,
+			Some(vec![0; MaxMetadataLen::get() as usize].try_into().unwrap())
+		));
+		for (player, movement, secret) in
+			[(ALICE, GameMovement::Rock, ALICE_SECRET), (BOB, GameMovement::Scissors, BOB_SECRET)]
+		{
+			assert_ok!(TemplateModule::play_game(
+				Origin::signed(player),
+				0,
+				commit(player, 0, movement, secret),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			));
+		}
+		play_round(GameMovement::Rock, GameMovement::Scissors, 0);
+
+		let encoded = Games::<Test>::hashed_key_for(0).len() +
+			TemplateModule::games(0).unwrap().encoded_size() +
+			2 * 0u64.encoded_size();
+		let bound = TemplateModule::per_game_storage_bytes() as usize;
+		assert!(encoded > 0);
+		assert!(encoded <= bound);
+		assert_eq!(
+			bound,
+			Games::<Test>::hashed_key_for(0).len() +
+				crate::GameStateOf::<Test>::max_encoded_len() +
+				2 * 0u64.encoded_size()
+		);
+	});
+}
+
+#[test]
+fn should_dry_run_play_with_the_errors_of_play_game() {
+	new_test_ext().execute_with(|| {
+		let join = |player: u64, game_id: GameId| {
+			assert_ok!(TemplateModule::play_game(
+				Origin::signed(player),
+				game_id,
+				commit(player, game_id, GameMovement::Rock, BOB_SECRET),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			));
+		};
+		create_full_game(GameMovement::Rock, GameMovement::Paper);
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			Some(BOB),
+			None,
+			None
+		));
+		join(ALICE, 1);
+		let dry_run = TemplateModule::dry_run_play;
+
+		assert_eq!(
+			dry_run(5, &BOB, BET_AMOUNT, NATIVE_ASSET),
+			Err(Error::<Test>::GameNotFound.into())
+		);
+		assert_eq!(
+			dry_run(0, &ALICE, BET_AMOUNT, NATIVE_ASSET),
+			Err(Error::<Test>::PlayerAlreadyInGame.into())
+		);
+		assert_eq!(
+			dry_run(0, &CHARLIE, BET_AMOUNT, NATIVE_ASSET),
+			Err(Error::<Test>::GameIsFull.into())
+		);
+		assert_eq!(
+			dry_run(1, &ALICE, BET_AMOUNT, NATIVE_ASSET),
+			Err(Error::<Test>::CannotPlaySelf.into())
+		);
+		assert_eq!(
+			dry_run(1, &CHARLIE, BET_AMOUNT, NATIVE_ASSET),
+			Err(Error::<Test>::NotInvited.into())
+		);
+		assert_eq!(dry_run(1, &BOB, 0, NATIVE_ASSET), Err(Error::<Test>::BetTooLow.into()));
+		assert_eq!(dry_run(1, &BOB, 10_001, NATIVE_ASSET), Err(Error::<Test>::BetTooHigh.into()));
+		assert_eq!(
+			dry_run(1, &BOB, BET_AMOUNT + 1, NATIVE_ASSET),
+			Err(Error::<Test>::StakeMismatch.into())
+		);
+		assert_eq!(
+			dry_run(1, &BOB, BET_AMOUNT, OTHER_ASSET),
+			Err(Error::<Test>::StakeMismatch.into())
+		);
+		assert_eq!(dry_run(1, &BOB, BET_AMOUNT, NATIVE_ASSET), Ok(()));
+		// Nothing was reserved nor seated by the dry runs.
+		assert_eq!(TemplateModule::games(1).unwrap().free_slots(), 1);
+		assert_eq!(Balances::reserved_balance(BOB), BET_AMOUNT);
+
+		for game_id in [2, 3] {
+			create_game_at(1);
+			join(BOB, game_id);
+		}
+		assert_eq!(
+			dry_run(1, &BOB, BET_AMOUNT, NATIVE_ASSET),
+			Err(Error::<Test>::TooManyGames.into())
+		);
+
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_eq!(dry_run(4, &CHARLIE, BET_AMOUNT, 7), Err(Error::<Test>::UnknownAsset.into()));
+		assert_eq!(
+			dry_run(4, &CHARLIE, INITIAL_BALANCE + 1, NATIVE_ASSET),
+			Err(Error::<Test>::InsufficientBalance.into())
+		);
+		assert_eq!(dry_run(4, &CHARLIE, BET_AMOUNT, NATIVE_ASSET), Ok(()));
+		assert_ok!(TemplateModule::block_account(Origin::root(), CHARLIE));
+		assert_eq!(
+			dry_run(4, &CHARLIE, BET_AMOUNT, NATIVE_ASSET),
+			Err(Error::<Test>::AccountBlocked.into())
+		);
+	});
+}
+
+#[test]
+fn should_transfer_the_ownership_of_a_game_until_it_fills() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			Some(CHARLIE),
+			None,
+			None
+		));
+		assert_noop!(
+			TemplateModule::transfer_ownership(Origin::signed(BOB), 0, CHARLIE),
+			Error::<Test>::NotGameOwner
+		);
+		assert_ok!(TemplateModule::transfer_ownership(Origin::signed(ALICE), 0, BOB));
+		System::assert_last_event(crate::Event::<Test>::OwnershipTransferred(0, ALICE, BOB).into());
+		assert_eq!(TemplateModule::games(0).unwrap().creator, Some(BOB));
+
+		// The seat of the creator in the private game went along with the ownership.
+		assert_eq!(
+			TemplateModule::dry_run_play(0, &ALICE, BET_AMOUNT, NATIVE_ASSET),
+			Err(Error::<Test>::NotInvited.into())
+		);
+		assert_noop!(
+			TemplateModule::set_min_accept(Origin::signed(ALICE), 0, 50),
+			Error::<Test>::NotGameOwner
+		);
+		assert_ok!(TemplateModule::set_min_accept(Origin::signed(BOB), 0, 50));
+		assert_noop!(
+			TemplateModule::set_reveal_window(Origin::signed(ALICE), 0, false),
+			Error::<Test>::NotGameOwner
+		);
+		assert_ok!(TemplateModule::set_reveal_window(Origin::signed(BOB), 0, false));
+
+		for (player, secret) in [(BOB, BOB_SECRET), (CHARLIE, ALICE_SECRET)] {
+			assert_ok!(TemplateModule::play_game(
+				Origin::signed(player),
+				0,
+				commit(player, 0, GameMovement::Rock, secret),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			));
+		}
+		assert_noop!(
+			TemplateModule::transfer_ownership(Origin::signed(BOB), 0, ALICE),
+			Error::<Test>::GameIsFull
+		);
+	});
+}
+
+#[test]
+fn should_not_transfer_the_ownership_of_a_game_to_a_blocked_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::block_account(Origin::root(), BOB));
+		assert_noop!(
+			TemplateModule::transfer_ownership(Origin::signed(ALICE), 0, BOB),
+			Error::<Test>::AccountBlocked
+		);
+	});
+}
+
+#[test]
+fn should_rank_wins_by_season() {
+	new_test_ext().execute_with(|| {
+		let alice = |movement| (ALICE, movement, ALICE_SECRET);
+		let bob = |movement| (BOB, movement, BOB_SECRET);
+		let first = play_single_round_game(alice(GameMovement::Paper), bob(GameMovement::Rock));
+
+		assert_noop!(TemplateModule::advance_season(Origin::signed(ALICE)), BadOrigin);
+		assert_ok!(TemplateModule::advance_season(Origin::root()));
+		System::assert_last_event(crate::Event::<Test>::SeasonAdvanced(1).into());
+		assert_eq!(TemplateModule::current_season(), 1);
+
+		let second =
+			play_single_round_game(bob(GameMovement::Scissors), alice(GameMovement::Paper));
+
+		assert_eq!(TemplateModule::season_wins(0, ALICE), 1);
+		assert_eq!(TemplateModule::season_wins(0, BOB), 0);
+		assert_eq!(TemplateModule::season_wins(1, ALICE), 0);
+		assert_eq!(TemplateModule::season_wins(1, BOB), 1);
+		assert_eq!((TemplateModule::wins(ALICE), TemplateModule::wins(BOB)), (1, 1));
+		assert_eq!(TemplateModule::outcome(first).unwrap().season, 0);
+		assert_eq!(TemplateModule::outcome(second).unwrap().season, 1);
+	});
+}
+
+#[test]
+fn should_pay_the_sponsorship_of_a_game_to_its_winner() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_game(
+			Origin::signed(ALICE),
+			GameMode::Classic,
+			1,
+			false,
+			None,
+			None,
+			None
+		));
+		assert_noop!(
+			TemplateModule::sponsor_game(Origin::signed(CHARLIE), 0, 50),
+			Error::<Test>::InvalidGameStatus
+		);
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(ALICE),
+			0,
+			commit(ALICE, 0, GameMovement::Paper, ALICE_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_ok!(TemplateModule::play_game(
+			Origin::signed(BOB),
+			0,
+			commit(BOB, 0, GameMovement::Rock, BOB_SECRET),
+			BET_AMOUNT,
+			NATIVE_ASSET,
+			None
+		));
+		assert_noop!(
+			TemplateModule::sponsor_game(Origin::signed(CHARLIE), 0, 0),
+			Error::<Test>::ZeroSponsorship
+		);
+		assert_noop!(
+			TemplateModule::sponsor_game(Origin::signed(CHARLIE), 0, INITIAL_BALANCE + 1),
+			Error::<Test>::InsufficientBalance
+		);
+		assert_ok!(TemplateModule::sponsor_game(Origin::signed(CHARLIE), 0, 50));
+		System::assert_last_event(crate::Event::<Test>::GameSponsored(0, CHARLIE, 50).into());
+		assert_noop!(
+			TemplateModule::sponsor_game(Origin::signed(ALICE), 0, 50),
+			Error::<Test>::AlreadySponsored
+		);
+		assert_eq!(TemplateModule::games(0).unwrap().pot, 2 * BET_AMOUNT + 50);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 50);
+
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Paper,
+			ALICE_SECRET,
+			true
+		));
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Rock,
+			BOB_SECRET,
+			true
+		));
+
+		// The house fee is only taken from the loser's bet.
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + PRIZE + 50);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - BET_AMOUNT);
+		assert_eq!(Balances::total_balance(&CHARLIE), INITIAL_BALANCE - 50);
+		assert_eq!(TemplateModule::sponsorship(0), None);
+		assert!(matches!(
+			System::events().last().map(|record| &record.event),
+			Some(Event::TemplateModule(crate::Event::GameFinished(0, _, Some(ALICE), prize, _)))
+				if *prize == PRIZE + 50
+		));
+	});
+}
+
+#[test]
+fn should_return_the_sponsorship_of_a_drawn_game() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Rock);
+		assert_ok!(TemplateModule::sponsor_game(Origin::signed(CHARLIE), 0, 50));
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(ALICE),
+			0,
+			GameMovement::Rock,
+			ALICE_SECRET,
+			true
+		));
+		assert_ok!(TemplateModule::reveal_move(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Rock,
+			BOB_SECRET,
+			true
+		));
+
+		assert_eq!(TemplateModule::games(0).unwrap().game_result, GameResult::Draw);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert_eq!(TemplateModule::sponsorship(0), None);
+	});
+}
+
+#[test]
+fn should_only_pay_players_when_a_non_participant_settles_a_draw() {
+	new_test_ext().execute_with(|| {
+		create_full_game(GameMovement::Rock, GameMovement::Rock);
+		assert_noop!(
+			TemplateModule::reveal_winner(
+				Origin::signed(CHARLIE),
+				0,
+				GameMovement::Rock,
+				ALICE_SECRET,
+				GameMovement::Rock,
+				BOB_SECRET
+			),
+			Error::<Test>::PlayerNotInGame
+		);
+		for (player, secret) in [(ALICE, ALICE_SECRET), (BOB, BOB_SECRET)] {
+			assert_ok!(TemplateModule::reveal_move(
+				Origin::signed(player),
+				0,
+				GameMovement::Rock,
+				secret,
+				false
+			));
+		}
+
+		// Anyone can settle the revealed game, but the bets only go back to the players.
+		assert_ok!(TemplateModule::settle_game(Origin::signed(CHARLIE), 0));
+		assert_eq!(TemplateModule::games(0).unwrap().game_result, GameResult::Draw);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(FEE_DESTINATION), 0);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn should_not_reveal_before_the_min_reveal_delay() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		MinRevealDelay::set(2);
+		create_full_game(GameMovement::Paper, GameMovement::Rock);
+		assert_noop!(
+			TemplateModule::reveal_move(
+				Origin::signed(ALICE),
+				0,
+				GameMovement::Paper,
+				ALICE_SECRET,
+				true
+			),
+			Error::<Test>::RevealTooSoon
+		);
+		assert_noop!(
+			TemplateModule::reveal_winner(
+				Origin::signed(BOB),
+				0,
+				GameMovement::Rock,
+				BOB_SECRET,
+				GameMovement::Paper,
+				ALICE_SECRET
+			),
+			Error::<Test>::RevealTooSoon
+		);
+
+		run_to_block(3);
+		assert_ok!(TemplateModule::reveal_winner(
+			Origin::signed(BOB),
+			0,
+			GameMovement::Rock,
+			BOB_SECRET,
+			GameMovement::Paper,
+			ALICE_SECRET
+		));
+		assert_eq!(TemplateModule::games(0).unwrap().winner, Some(ALICE));
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn should_sort_the_games_of_a_player_on_their_dashboard() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		let join = |player: u64, game_id: GameId, movement: GameMovement, secret: Secret| {
+			assert_ok!(TemplateModule::play_game(
+				Origin::signed(player),
+				game_id,
+				commit(player, game_id, movement, secret),
+				BET_AMOUNT,
+				NATIVE_ASSET,
+				None
+			));
+		};
+		let reveal = |player: u64, game_id: GameId, movement: GameMovement, secret: Secret| {
+			assert_ok!(TemplateModule::reveal_move(
+				Origin::signed(player),
+				game_id,
+				movement,
+				secret,
+				false
+			));
+		};
+		let finished = play_single_round_game(
+			(ALICE, GameMovement::Paper, ALICE_SECRET),
+			(BOB, GameMovement::Rock, BOB_SECRET),
+		);
+		for _ in 0..3 {
+			assert_ok!(TemplateModule::create_game(
+				Origin::signed(ALICE),
+				GameMode::Classic,
+				1,
+				false,
+				None,
+				None,
+				None
+			));
+		}
+		let (waiting, revealed, to_reveal) = (1, 2, 3);
+		join(ALICE, waiting, GameMovement::Rock, ALICE_SECRET);
+		for game_id in [revealed, to_reveal] {
+			join(ALICE, game_id, GameMovement::Rock, ALICE_SECRET);
+			join(BOB, game_id, GameMovement::Paper, BOB_SECRET);
+			reveal(BOB, game_id, GameMovement::Paper, BOB_SECRET);
+		}
+		reveal(ALICE, revealed, GameMovement::Rock, ALICE_SECRET);
+
+		assert_eq!(
+			TemplateModule::player_dashboard(&ALICE),
+			PlayerDashboard {
+				to_reveal: vec![to_reveal],
+				waiting_opponent: vec![waiting],
+				claimable: vec![revealed],
+				finished: vec![finished],
+			}
+		);
+		assert_eq!(
+			TemplateModule::player_dashboard(&BOB),
+			PlayerDashboard {
+				to_reveal: vec![],
+				waiting_opponent: vec![to_reveal],
+				claimable: vec![revealed],
+				finished: vec![finished],
+			}
+		);
+		assert_eq!(TemplateModule::player_dashboard(&CHARLIE), PlayerDashboard::default());
+
+		// Once the reveal deadline passed, the timeout can be claimed.
+		run_to_block(12);
+		assert_eq!(TemplateModule::player_dashboard(&BOB).claimable, vec![revealed, to_reveal]);
+		assert_eq!(TemplateModule::player_dashboard(&ALICE).to_reveal, vec![to_reveal]);
 	});
 }
@@ -0,0 +1,444 @@
+//! Weights for pallet_template
+//!
+//! These are estimates derived from the storage accessed by each call and should be replaced by
+//! the output of the benchmarks once they are run on reference hardware:
+//!
+//! ./target/release/node-template benchmark pallet --chain dev --execution wasm
+//! --wasm-execution compiled --pallet pallet_template --extrinsic '*' --steps 50 --repeat 20
+//! --output pallets/template/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use frame_support::sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_template.
+pub trait WeightInfo {
+	fn create_game() -> Weight;
+	fn create_games_batch(n: u32, ) -> Weight;
+	fn play_game() -> Weight;
+	fn commit_move() -> Weight;
+	fn recommit() -> Weight;
+	fn cancel_game() -> Weight;
+	fn reveal_winner() -> Weight;
+	fn reveal_move() -> Weight;
+	fn settle_game() -> Weight;
+	fn settle_games_batch(n: u32, ) -> Weight;
+	fn claim_timeout() -> Weight;
+	fn claim_no_show() -> Weight;
+	fn resolve_stalemate() -> Weight;
+	fn forfeit() -> Weight;
+	fn purge_settled(n: u32, ) -> Weight;
+	fn set_reveal_window() -> Weight;
+	fn request_rematch() -> Weight;
+	fn block_account() -> Weight;
+	fn unblock_account() -> Weight;
+	fn cancel_inactive_game() -> Weight;
+	fn set_min_accept() -> Weight;
+	fn transfer_ownership() -> Weight;
+	fn advance_season() -> Weight;
+	fn sponsor_game() -> Weight;
+}
+
+/// Weights for pallet_template using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: TemplateModule NextGameId (r:1 w:1)
+	// Storage: TemplateModule OpenGames (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule GamesCreatedInBlock (r:1 w:1)
+	// Storage: TemplateModule Blocked (r:1 w:0)
+	// Storage: TemplateModule Games (r:0 w:1)
+	fn create_game() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: TemplateModule NextGameId (r:1 w:1)
+	// Storage: TemplateModule OpenGames (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule GamesCreatedInBlock (r:1 w:1)
+	// Storage: TemplateModule Blocked (r:1 w:0)
+	// Storage: TemplateModule Games (r:0 w:1)
+	fn create_games_batch(n: u32, ) -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add((30_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	// Storage: TemplateModule PlayerGames (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule Blocked (r:1 w:0)
+	// Storage: TemplateModule EventSeq (r:1 w:1)
+	// Storage: TemplateModule MinAccepts (r:1 w:1)
+	fn play_game() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	// Storage: TemplateModule EventSeq (r:1 w:1)
+	fn commit_move() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	// Storage: TemplateModule EventSeq (r:1 w:1)
+	fn recommit() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule PlayerGames (r:1 w:1)
+	// Storage: TemplateModule OpenGames (r:1 w:1)
+	// Storage: TemplateModule EventSeq (r:0 w:1)
+	fn cancel_game() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: TemplateModule PlayerGames (r:2 w:2)
+	// Storage: TemplateModule OpenGames (r:1 w:1)
+	// Storage: TemplateModule HeadToHeadRecords (r:1 w:1)
+	// Storage: TemplateModule Wins (r:1 w:1)
+	// Storage: TemplateModule EventSeq (r:1 w:1)
+	// Storage: TemplateModule OutcomeQueue (r:1 w:1)
+	// Storage: TemplateModule Outcomes (r:0 w:2)
+	// Storage: TemplateModule CurrentSeason (r:1 w:0)
+	// Storage: TemplateModule SeasonWins (r:1 w:1)
+	// Storage: TemplateModule Sponsorships (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reveal_winner() -> Weight {
+		(110_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: TemplateModule PlayerGames (r:2 w:2)
+	// Storage: TemplateModule OpenGames (r:1 w:1)
+	// Storage: TemplateModule HeadToHeadRecords (r:1 w:1)
+	// Storage: TemplateModule Wins (r:1 w:1)
+	// Storage: TemplateModule EventSeq (r:1 w:1)
+	// Storage: TemplateModule OutcomeQueue (r:1 w:1)
+	// Storage: TemplateModule Outcomes (r:0 w:2)
+	// Storage: TemplateModule CurrentSeason (r:1 w:0)
+	// Storage: TemplateModule SeasonWins (r:1 w:1)
+	// Storage: TemplateModule Sponsorships (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reveal_move() -> Weight {
+		(95_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: TemplateModule PlayerGames (r:2 w:2)
+	// Storage: TemplateModule OpenGames (r:1 w:1)
+	// Storage: TemplateModule HeadToHeadRecords (r:1 w:1)
+	// Storage: TemplateModule Wins (r:1 w:1)
+	// Storage: TemplateModule EventSeq (r:1 w:1)
+	// Storage: TemplateModule OutcomeQueue (r:1 w:1)
+	// Storage: TemplateModule Outcomes (r:0 w:2)
+	// Storage: TemplateModule CurrentSeason (r:1 w:0)
+	// Storage: TemplateModule SeasonWins (r:1 w:1)
+	// Storage: TemplateModule Sponsorships (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn settle_game() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: TemplateModule PlayerGames (r:2 w:2)
+	// Storage: TemplateModule OpenGames (r:1 w:1)
+	// Storage: TemplateModule HeadToHeadRecords (r:1 w:1)
+	// Storage: TemplateModule Wins (r:1 w:1)
+	// Storage: TemplateModule EventSeq (r:1 w:1)
+	// Storage: TemplateModule OutcomeQueue (r:1 w:1)
+	// Storage: TemplateModule Outcomes (r:0 w:2)
+	// Storage: TemplateModule CurrentSeason (r:1 w:0)
+	// Storage: TemplateModule SeasonWins (r:1 w:1)
+	// Storage: TemplateModule Sponsorships (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn settle_games_batch(n: u32, ) -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add((60_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((14 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((16 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: TemplateModule PlayerGames (r:2 w:2)
+	// Storage: TemplateModule OpenGames (r:1 w:1)
+	// Storage: TemplateModule HeadToHeadRecords (r:1 w:1)
+	// Storage: TemplateModule Wins (r:1 w:1)
+	// Storage: TemplateModule EventSeq (r:1 w:1)
+	// Storage: TemplateModule OutcomeQueue (r:1 w:1)
+	// Storage: TemplateModule Outcomes (r:0 w:2)
+	// Storage: TemplateModule CurrentSeason (r:1 w:0)
+	// Storage: TemplateModule SeasonWins (r:1 w:1)
+	// Storage: TemplateModule Sponsorships (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn claim_timeout() -> Weight {
+		(80_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule PlayerGames (r:1 w:1)
+	// Storage: TemplateModule OpenGames (r:1 w:1)
+	// Storage: TemplateModule EventSeq (r:1 w:1)
+	// Storage: TemplateModule OutcomeQueue (r:1 w:1)
+	// Storage: TemplateModule Outcomes (r:0 w:2)
+	// Storage: TemplateModule CurrentSeason (r:1 w:0)
+	fn claim_no_show() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: TemplateModule PlayerGames (r:2 w:2)
+	// Storage: TemplateModule OpenGames (r:1 w:1)
+	// Storage: TemplateModule OutcomeQueue (r:1 w:1)
+	// Storage: TemplateModule Outcomes (r:0 w:2)
+	// Storage: TemplateModule CurrentSeason (r:1 w:0)
+	// Storage: TemplateModule Sponsorships (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn resolve_stalemate() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: TemplateModule PlayerGames (r:2 w:2)
+	// Storage: TemplateModule OpenGames (r:1 w:1)
+	// Storage: TemplateModule HeadToHeadRecords (r:1 w:1)
+	// Storage: TemplateModule Wins (r:1 w:1)
+	// Storage: TemplateModule EventSeq (r:1 w:1)
+	// Storage: TemplateModule OutcomeQueue (r:1 w:1)
+	// Storage: TemplateModule Outcomes (r:0 w:2)
+	// Storage: TemplateModule CurrentSeason (r:1 w:0)
+	// Storage: TemplateModule SeasonWins (r:1 w:1)
+	// Storage: TemplateModule Sponsorships (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn forfeit() -> Weight {
+		(75_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	// Storage: TemplateModule RematchRequests (r:0 w:1)
+	// Storage: TemplateModule Rematches (r:0 w:1)
+	// Storage: TemplateModule EventSeq (r:0 w:1)
+	fn purge_settled(n: u32, ) -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add((7_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	fn set_reveal_window() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TemplateModule Games (r:2 w:2)
+	// Storage: TemplateModule Rematches (r:1 w:1)
+	// Storage: TemplateModule RematchRequests (r:1 w:1)
+	// Storage: TemplateModule NextGameId (r:1 w:1)
+	// Storage: TemplateModule OpenGames (r:1 w:1)
+	// Storage: TemplateModule GamesCreatedInBlock (r:1 w:1)
+	// Storage: TemplateModule PlayerGames (r:2 w:2)
+	// Storage: System Account (r:3 w:3)
+	fn request_rematch() -> Weight {
+		(90_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
+	// Storage: TemplateModule Blocked (r:0 w:1)
+	fn block_account() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TemplateModule Blocked (r:0 w:1)
+	fn unblock_account() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	// Storage: TemplateModule PlayerGames (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule OpenGames (r:1 w:1)
+	// Storage: TemplateModule EventSeq (r:0 w:1)
+	fn cancel_inactive_game() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: TemplateModule Games (r:1 w:0)
+	// Storage: TemplateModule MinAccepts (r:0 w:1)
+	fn set_min_accept() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TemplateModule Blocked (r:1 w:0)
+	// Storage: TemplateModule Games (r:1 w:1)
+	fn transfer_ownership() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TemplateModule CurrentSeason (r:1 w:1)
+	fn advance_season() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TemplateModule Games (r:1 w:1)
+	// Storage: TemplateModule Sponsorships (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn sponsor_game() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_game() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn create_games_batch(n: u32, ) -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add((30_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+	fn play_game() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn commit_move() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn recommit() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn cancel_game() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn reveal_winner() -> Weight {
+		(110_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+	}
+	fn reveal_move() -> Weight {
+		(95_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+	}
+	fn settle_game() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+	}
+	fn settle_games_batch(n: u32, ) -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add((60_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((14 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((16 as Weight).saturating_mul(n as Weight)))
+	}
+	fn claim_timeout() -> Weight {
+		(80_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+	}
+	fn claim_no_show() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn resolve_stalemate() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+	}
+	fn forfeit() -> Weight {
+		(75_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+	}
+	fn purge_settled(n: u32, ) -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add((7_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	fn set_reveal_window() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn request_rematch() -> Weight {
+		(90_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+	}
+	fn block_account() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn unblock_account() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn cancel_inactive_game() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn set_min_accept() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn transfer_ownership() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn advance_season() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn sponsor_game() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+}
@@ -27,7 +27,8 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo,
+		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness,
+		StorageInfo,
 	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
@@ -261,9 +262,49 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+parameter_types! {
+	/// The node template takes no house fee on games.
+	pub const HouseFee: Permill = Permill::zero();
+	pub FeeDestination: AccountId = AccountId::new([0u8; 32]);
+	/// Players get their stakes back when a game ends in a draw.
+	pub const GameDrawPolicy: pallet_template::game_logic::DrawPolicy =
+		pallet_template::game_logic::DrawPolicy::SplitPot;
+	/// A player who forfeits or times out loses their whole bet to their opponent.
+	pub const ForfeitPenalty: Permill = Permill::from_percent(100);
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
 	type Event = Event;
+	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	type Assets = pallet_template::multi_currency::NativeCurrency<Balances>;
+	type HouseFee = HouseFee;
+	type FeeDestination = FeeDestination;
+	type GameCreationFee = ConstU128<500>;
+	type MinBet = ConstU128<500>;
+	type MaxBet = ConstU128<1_000_000_000_000_000>;
+	type MinSecret = ConstU64<1_000_000>;
+	type MaxSecret = ConstU64<{ u64::MAX }>;
+	type MaxRounds = ConstU8<9>;
+	type RevealTimeout = ConstU32<{ 10 * MINUTES }>;
+	type MinTimeout = ConstU32<{ MINUTES }>;
+	type MaxTimeout = ConstU32<{ DAYS }>;
+	type JoinTimeout = ConstU32<{ 5 * MINUTES }>;
+	type MaxGamesPerPlayer = ConstU32<32>;
+	type MaxDrawReplays = ConstU8<3>;
+	type DrawPolicy = GameDrawPolicy;
+	type AllowDraws = ConstBool<true>;
+	type MaxOpenGames = ConstU32<1_000>;
+	type MaxExpirySweep = ConstU32<16>;
+	type InactivityTimeout = ConstU32<{ 8 * MINUTES }>;
+	type MaxGamesPerBlockPerAccount = ConstU32<4>;
+	type MaxBatch = ConstU32<4>;
+	type MaxGameLifetime = ConstU32<{ 30 * DAYS }>;
+	type MaxOutcomes = ConstU32<1_000>;
+	type RejectSharedSecret = ConstBool<false>;
+	type ForfeitPenalty = ForfeitPenalty;
+	type MinRevealDelay = ConstU32<1>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -462,6 +503,33 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl
+		pallet_template::runtime_api::RockPaperScissorsApi<
+			Block,
+			AccountId,
+			Balance,
+			BlockNumber,
+			pallet_template::MaxRoundsOf<Runtime>,
+		> for Runtime
+	{
+		fn games_for_account(account: AccountId) -> Vec<pallet_template::game_logic::GameId> {
+			TemplateModule::games_for_account(&account)
+		}
+
+		fn game_details(
+			game_id: pallet_template::game_logic::GameId,
+		) -> Option<pallet_template::GameDetailsOf<Runtime>> {
+			TemplateModule::game_details(game_id)
+		}
+
+		fn preview_result(
+			player1: pallet_template::game_logic::GameMovement,
+			player2: pallet_template::game_logic::GameMovement,
+		) -> (pallet_template::game_logic::GameResult, pallet_template::game_logic::Winner) {
+			TemplateModule::preview_result(player1, player2)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (